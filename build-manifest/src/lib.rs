@@ -0,0 +1,180 @@
+//! Records what every site generator read and wrote in a shared `build_manifest.json`,
+//! so two runs can be compared with `cargo run -p build-manifest -- <generator>`.
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use walkdir::WalkDir;
+
+/// Overrides where the manifest is written, used to keep the runs being verified apart
+pub const MANIFEST_ENV: &str = "BUILD_MANIFEST";
+const DEFAULT_MANIFEST: &str = "build_manifest.json";
+/// Seconds since the Unix epoch that generators use as the current time, see
+/// <https://reproducible-builds.org/specs/source-date-epoch/>
+pub const SOURCE_DATE_EPOCH: &str = "SOURCE_DATE_EPOCH";
+
+/// Starts recording a run of the calling generator, with its crate name and version
+#[macro_export]
+macro_rules! start {
+    () => {
+        $crate::Run::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+    };
+}
+
+/// The manifest maps each generator to its latest run
+pub type Manifest = BTreeMap<String, Entry>;
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Entry {
+    pub version: String,
+    pub inputs: Vec<Input>,
+    /// Hash of the command line arguments and the contents of the config files
+    pub config_hash: String,
+    /// Hash of every file written, directories are listed file by file
+    pub outputs: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Input {
+    pub path: String,
+    /// Last commit that changed the input, if it's tracked
+    pub commit: Option<String>,
+    /// Whether the input has uncommitted changes
+    pub dirty: bool,
+}
+
+pub struct Run {
+    tool: &'static str,
+    version: &'static str,
+    inputs: BTreeSet<PathBuf>,
+    config: Sha256,
+    outputs: BTreeSet<PathBuf>,
+}
+
+impl Run {
+    pub fn new(tool: &'static str, version: &'static str) -> Self {
+        let mut config = Sha256::new();
+        for arg in env::args().skip(1) {
+            config.update(arg.as_bytes());
+            config.update([0]);
+        }
+        Run {
+            tool,
+            version,
+            inputs: BTreeSet::new(),
+            config,
+            outputs: BTreeSet::new(),
+        }
+    }
+
+    /// A file or folder the output is generated from, identified by its git commit
+    pub fn input(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        self.inputs.insert(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// A config file, hashed by content since small edits to it change the output
+    pub fn config(&mut self, path: impl AsRef<Path>) -> anyhow::Result<&mut Self> {
+        let path = path.as_ref();
+        let contents = fs::read(path).with_context(|| format!("failed to read {:?}", path))?;
+        self.config.update(slash_path(path).as_bytes());
+        self.config.update([0]);
+        self.config.update(&contents);
+        self.input(path);
+        Ok(self)
+    }
+
+    pub fn output(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        self.outputs.insert(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Adds the run to the manifest, replacing the previous run of the same generator
+    pub fn finish(self) -> anyhow::Result<()> {
+        let mut outputs = BTreeMap::new();
+        for output in &self.outputs {
+            for entry in WalkDir::new(output).sort_by_file_name() {
+                let entry = entry.with_context(|| format!("failed to read {:?}", output))?;
+                if entry.file_type().is_file() {
+                    let bytes = fs::read(entry.path())?;
+                    outputs.insert(
+                        slash_path(entry.path()),
+                        format!("{:x}", Sha256::digest(&bytes)),
+                    );
+                }
+            }
+        }
+
+        let entry = Entry {
+            version: self.version.to_string(),
+            inputs: self.inputs.iter().map(|path| git_input(path)).collect(),
+            config_hash: format!("{:x}", self.config.finalize()),
+            outputs,
+        };
+
+        let path = manifest_path();
+        let mut manifest = read(&path)?;
+        manifest.insert(self.tool.to_string(), entry);
+        fs::write(&path, serde_json::to_string_pretty(&manifest)? + "\n")
+            .with_context(|| format!("failed to write {:?}", path))
+    }
+}
+
+/// The current time in UTC, or `SOURCE_DATE_EPOCH` when it's set so two runs write the same output
+pub fn now() -> anyhow::Result<DateTime<Utc>> {
+    let Some(epoch) = env::var_os(SOURCE_DATE_EPOCH) else {
+        return Ok(Utc::now());
+    };
+    let epoch = epoch.to_string_lossy();
+    epoch
+        .trim()
+        .parse()
+        .ok()
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .with_context(|| format!("{SOURCE_DATE_EPOCH} `{epoch}` isn't a Unix timestamp"))
+}
+
+pub fn manifest_path() -> PathBuf {
+    env::var_os(MANIFEST_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_MANIFEST))
+}
+
+/// Reads a manifest, which is empty if it doesn't exist yet
+pub fn read(path: &Path) -> anyhow::Result<Manifest> {
+    if !path.exists() {
+        return Ok(Manifest::new());
+    }
+    let source = fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?;
+    serde_json::from_str(&source).with_context(|| format!("failed to parse {:?}", path))
+}
+
+fn git_input(path: &Path) -> Input {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .arg("--")
+            .arg(path)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    Input {
+        path: slash_path(path),
+        commit: git(&["log", "-1", "--format=%H"]).filter(|commit| !commit.is_empty()),
+        dirty: git(&["status", "--porcelain"]).is_some_and(|status| !status.is_empty()),
+    }
+}
+
+/// Paths use `/` so manifests written on Windows compare equal to the others
+fn slash_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
@@ -0,0 +1,114 @@
+use anyhow::{bail, Context};
+use build_manifest::{Entry, SOURCE_DATE_EPOCH};
+use clap::Parser;
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::Path,
+    process::{Command, ExitCode},
+};
+
+#[derive(Parser)]
+#[command(about = "Run a generator twice and check that both runs produce identical output")]
+struct Args {
+    /// Package name of the generator, e.g. `generate-feeds`
+    generator: String,
+    /// Arguments passed to both runs, after `--`
+    #[arg(last = true)]
+    args: Vec<String>,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let args = Args::parse();
+
+    // Both runs see the same time, so only real differences in the output are reported
+    let source_date_epoch = build_manifest::now()?.timestamp().to_string();
+
+    let dir = Path::new("target/build-manifest");
+    fs::create_dir_all(dir)?;
+    // The previous run of the generator in the shared manifest, if any
+    let mut previous =
+        build_manifest::read(&build_manifest::manifest_path())?.remove(&args.generator);
+    let mut runs = Vec::new();
+    for run in ["first", "second"] {
+        // Generators skip outputs that are newer than their inputs, which would make the second
+        // run compare the files written by the first one with themselves
+        if let Some(previous) = &previous {
+            remove_outputs(previous)?;
+        }
+
+        let path = dir.join(format!("{run}.json"));
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        let status = Command::new("cargo")
+            .args(["run", "--quiet", "-p", &args.generator, "--"])
+            .args(&args.args)
+            .env(build_manifest::MANIFEST_ENV, &path)
+            .env(SOURCE_DATE_EPOCH, &source_date_epoch)
+            .status()
+            .context("failed to run cargo")?;
+        if !status.success() {
+            bail!("the {run} run of {} failed with {status}", args.generator);
+        }
+        let Some(entry) = build_manifest::read(&path)?.remove(&args.generator) else {
+            bail!(
+                "{} doesn't record its runs in the build manifest",
+                args.generator
+            );
+        };
+        previous = Some(entry.clone());
+        runs.push(entry);
+    }
+    let (first, second) = (&runs[0], &runs[1]);
+
+    if first.inputs != second.inputs || first.config_hash != second.config_hash {
+        bail!("the inputs changed between the two runs, commit or stash them and try again");
+    }
+
+    let paths: BTreeSet<_> = first.outputs.keys().chain(second.outputs.keys()).collect();
+    let mut differences = 0;
+    for path in paths {
+        let difference = match (first.outputs.get(path), second.outputs.get(path)) {
+            (Some(a), Some(b)) if a == b => continue,
+            (Some(_), Some(_)) => "the contents differ",
+            (Some(_), None) => "only written by the first run",
+            (None, _) => "only written by the second run",
+        };
+        println!("{path}: {difference}");
+        differences += 1;
+    }
+
+    if differences > 0 {
+        println!(
+            "{} isn't reproducible, {differences} of its outputs differ between runs",
+            args.generator
+        );
+        return Ok(ExitCode::FAILURE);
+    }
+    println!(
+        "{} is reproducible, both runs wrote the same {} files",
+        args.generator,
+        first.outputs.len()
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Deletes the generated files written by a run. Files it also reads (like a history it appends
+/// to) and tracked files (like posts it rewrites in place) are kept.
+fn remove_outputs(entry: &Entry) -> anyhow::Result<()> {
+    let tracked = Command::new("git")
+        .args(["ls-files", "-z"])
+        .output()
+        .context("failed to run git")?
+        .stdout;
+    let tracked: BTreeSet<&[u8]> = tracked.split(|b| *b == 0).collect();
+    for output in entry.outputs.keys() {
+        let is_input = entry.inputs.iter().any(|input| &input.path == output);
+        let path = Path::new(output);
+        if !is_input && !tracked.contains(output.as_bytes()) && path.exists() {
+            fs::remove_file(path).with_context(|| format!("failed to remove {:?}", path))?;
+        }
+    }
+    Ok(())
+}
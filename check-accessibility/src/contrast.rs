@@ -0,0 +1,144 @@
+/// WCAG 2.1 level AA minimum for normal sized text
+pub const MIN_CONTRAST_RATIO: f64 = 4.5;
+
+#[derive(Clone, Copy)]
+pub struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+/// The text and background colors set by an inline `style` attribute
+pub fn inline_colors(style: &str) -> (Option<Color>, Option<Color>) {
+    let (mut text, mut background) = (None, None);
+    for declaration in style.split(';') {
+        let Some((property, value)) = declaration.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().trim_end_matches("!important").trim();
+        match property.trim().to_lowercase().as_str() {
+            "color" => text = parse(value),
+            // `background` is only understood when it's a single color
+            "background-color" | "background" => background = parse(value),
+            _ => {}
+        }
+    }
+    (text, background)
+}
+
+/// Ratio between 1 and 21, as defined by WCAG
+pub fn contrast_ratio(a: Color, b: Color) -> f64 {
+    let (a, b) = (luminance(a), luminance(b));
+    let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+fn luminance(color: Color) -> f64 {
+    let channel = |value: u8| {
+        let value = f64::from(value) / 255.0;
+        if value <= 0.03928 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(color.r) + 0.7152 * channel(color.g) + 0.0722 * channel(color.b)
+}
+
+/// Hex, `rgb()` and a few named colors. Anything else (variables, gradients, ...) is skipped.
+fn parse(value: &str) -> Option<Color> {
+    let value = value.to_lowercase();
+    if let Some(hex) = value.strip_prefix('#') {
+        let digits: Vec<u8> = hex
+            .chars()
+            .map(|c| c.to_digit(16).map(|d| d as u8))
+            .collect::<Option<_>>()?;
+        return match digits[..] {
+            [r, g, b] => Some(Color {
+                r: r * 17,
+                g: g * 17,
+                b: b * 17,
+            }),
+            [r1, r2, g1, g2, b1, b2] => Some(Color {
+                r: r1 * 16 + r2,
+                g: g1 * 16 + g2,
+                b: b1 * 16 + b2,
+            }),
+            _ => None,
+        };
+    }
+    if let Some(arguments) = value
+        .strip_prefix("rgb(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        let channels: Vec<u8> = arguments
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|channel| !channel.is_empty())
+            .map(|channel| channel.parse().ok())
+            .collect::<Option<_>>()?;
+        return match channels[..] {
+            [r, g, b] => Some(Color { r, g, b }),
+            _ => None,
+        };
+    }
+    let (r, g, b) = match value.as_str() {
+        "white" => (255, 255, 255),
+        "black" => (0, 0, 0),
+        "gray" | "grey" => (128, 128, 128),
+        "silver" => (192, 192, 192),
+        "red" => (255, 0, 0),
+        "yellow" => (255, 255, 0),
+        _ => return None,
+    };
+    Some(Color { r, g, b })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ratio(a: &str, b: &str) -> f64 {
+        contrast_ratio(parse(a).unwrap(), parse(b).unwrap())
+    }
+
+    #[test]
+    fn parses_colors() {
+        for value in [
+            "#fff",
+            "#FFFFFF",
+            "rgb(255, 255, 255)",
+            "rgb(255 255 255)",
+            "white",
+        ] {
+            let color = parse(value).unwrap();
+            assert_eq!((color.r, color.g, color.b), (255, 255, 255), "{value}");
+        }
+        for value in [
+            "#ffff",
+            "rgb(255, 255)",
+            "var(--text)",
+            "linear-gradient(red, blue)",
+        ] {
+            assert!(parse(value).is_none(), "{value}");
+        }
+    }
+
+    #[test]
+    fn matches_wcag_ratios() {
+        assert!((ratio("black", "white") - 21.0).abs() < 1e-9);
+        assert!((ratio("#fff", "#fff") - 1.0).abs() < 1e-9);
+        // The lightest gray that passes AA on white
+        assert!((ratio("#767676", "#fff") - 4.54).abs() < 0.01);
+        assert!(ratio("#777777", "#fff") < MIN_CONTRAST_RATIO);
+        assert!((ratio("red", "white") - 4.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn reads_inline_styles() {
+        let (text, background) =
+            inline_colors("color: #000 !important; background: #fff; padding: 0");
+        assert_eq!(text.map(|c| c.r), Some(0));
+        assert_eq!(background.map(|c| c.r), Some(255));
+        assert!(inline_colors("margin: 0").0.is_none());
+    }
+}
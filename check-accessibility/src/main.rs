@@ -0,0 +1,144 @@
+use anyhow::Context;
+use clap::Parser;
+use contrast::{Color, MIN_CONTRAST_RATIO};
+use scraper::{ElementRef, Html, Selector};
+use std::{collections::BTreeMap, fs, path::PathBuf, process::ExitCode};
+use walkdir::WalkDir;
+
+mod contrast;
+
+#[derive(Parser)]
+#[command(about = "Check built pages for missing alt text, skipped headings and low contrast")]
+struct Args {
+    /// Output folder of `zola build`
+    #[arg(long, default_value = "public")]
+    public_dir: PathBuf,
+}
+
+struct Selectors {
+    images: Selector,
+    headings: Selector,
+    styled: Selector,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let args = Args::parse();
+    let selectors = Selectors {
+        images: Selector::parse("img").unwrap(),
+        headings: Selector::parse("h1, h2, h3, h4, h5, h6").unwrap(),
+        styled: Selector::parse("[style]").unwrap(),
+    };
+
+    let mut issues: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+    let mut pages = 0;
+    for entry in WalkDir::new(&args.public_dir).sort_by_file_name() {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "html") {
+            continue;
+        }
+        let html = Html::parse_document(
+            &fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?,
+        );
+        pages += 1;
+
+        let page_issues = audit(&html, &selectors);
+        if !page_issues.is_empty() {
+            issues.insert(path.to_path_buf(), page_issues);
+        }
+    }
+
+    println!("audited {pages} pages");
+    if issues.is_empty() {
+        return Ok(ExitCode::SUCCESS);
+    }
+    for (file, file_issues) in &issues {
+        println!("{}:", file.display());
+        for issue in file_issues {
+            println!("    {issue}");
+        }
+    }
+    println!(
+        "found {} accessibility issues",
+        issues.values().map(Vec::len).sum::<usize>()
+    );
+    Ok(ExitCode::FAILURE)
+}
+
+fn audit(html: &Html, selectors: &Selectors) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    for image in html.select(&selectors.images) {
+        let element = image.value();
+        // An empty `alt` marks a decorative image, which is fine, a missing one isn't
+        let hidden = element.attr("aria-hidden") == Some("true")
+            || element.attr("role") == Some("presentation");
+        if element.attr("alt").is_none() && !hidden {
+            issues.push(format!(
+                "image {} has no alt text",
+                element.attr("src").unwrap_or("without src")
+            ));
+        }
+    }
+
+    let mut previous_level = None;
+    for heading in html.select(&selectors.headings) {
+        let level = heading.value().name()[1..].parse::<u8>().unwrap();
+        if let Some(previous) = previous_level {
+            if level > previous + 1 {
+                issues.push(format!(
+                    "heading \"{}\" skips from h{previous} to h{level}",
+                    text(heading)
+                ));
+            }
+        }
+        previous_level = Some(level);
+    }
+
+    for element in html.select(&selectors.styled) {
+        let (own_text, own_background) =
+            contrast::inline_colors(element.value().attr("style").unwrap());
+        if own_text.is_none() && own_background.is_none() {
+            continue;
+        }
+        let (Some(text_color), Some(background)) = (
+            own_text.or_else(|| inherited(element, |(text, _)| text)),
+            own_background.or_else(|| inherited(element, |(_, background)| background)),
+        ) else {
+            continue;
+        };
+        let ratio = contrast::contrast_ratio(text_color, background);
+        if ratio < MIN_CONTRAST_RATIO {
+            issues.push(format!(
+                "<{}> \"{}\" has a contrast ratio of {ratio:.2}, below {MIN_CONTRAST_RATIO}",
+                element.value().name(),
+                text(element)
+            ));
+        }
+    }
+
+    issues
+}
+
+/// The closest color set by the inline style of an ancestor
+fn inherited(
+    element: ElementRef,
+    pick: impl Fn((Option<Color>, Option<Color>)) -> Option<Color>,
+) -> Option<Color> {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .filter_map(|ancestor| ancestor.value().attr("style"))
+        .find_map(|style| pick(contrast::inline_colors(style)))
+}
+
+/// The start of the element's text, to find it in the page
+fn text(element: ElementRef) -> String {
+    let text = element.text().collect::<Vec<_>>().join(" ");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > 60 {
+        format!("{}…", text.chars().take(60).collect::<String>())
+    } else {
+        text
+    }
+}
@@ -0,0 +1,279 @@
+use anyhow::{bail, Context};
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use pulldown_cmark::{Event, Parser as Markdown, Tag};
+use reqwest::Method;
+use scraper::{Html, Selector};
+use serde::Deserialize;
+use site_content::read_toml;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+use url::Url;
+use walkdir::WalkDir;
+
+const DISCORD_API_URL: &str = "https://discord.com/api/v10";
+
+/// Answers for Twitter profiles and tweets without the scripts and rate limits of the site itself
+const TWITTER_OEMBED_URL: &str = "https://publish.twitter.com/oembed";
+
+/// Answers for YouTube videos and playlists, but not for channels
+const YOUTUBE_OEMBED_URL: &str = "https://www.youtube.com/oembed";
+
+#[derive(Parser)]
+#[command(about = "Check that the community links shown on the site still resolve")]
+struct Args {
+    /// Templates holding the header, footer and community page links
+    #[arg(long, default_value = "templates")]
+    templates_dir: PathBuf,
+    /// Markdown whose links point to tutorials and other community resources
+    #[arg(long, default_values = ["content/community", "content/learn"])]
+    content_dirs: Vec<PathBuf>,
+    /// Hosts known to block automated requests, shared with `check-links`
+    #[arg(long, default_value = "check-links/allowlist.toml")]
+    allowlist: PathBuf,
+    /// Report Discord invites expiring within this many days
+    #[arg(long, default_value_t = 14)]
+    expiry_warning_days: i64,
+}
+
+#[derive(Deserialize)]
+struct Allowlist {
+    hosts: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct DiscordInvite {
+    expires_at: Option<DateTime<Utc>>,
+    guild: Option<DiscordGuild>,
+}
+
+#[derive(Deserialize)]
+struct DiscordGuild {
+    name: String,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let args = Args::parse();
+
+    let allowlist: Allowlist = read_toml(&args.allowlist)?;
+    // Every external link, with the first file it was found in
+    let mut links: BTreeMap<Url, PathBuf> = BTreeMap::new();
+    for (path, link) in template_links(&args.templates_dir)?
+        .into_iter()
+        .chain(markdown_links(&args.content_dirs)?)
+    {
+        if let Ok(url) = Url::parse(&link) {
+            if matches!(url.scheme(), "http" | "https") {
+                links.entry(url).or_insert(path);
+            }
+        }
+    }
+
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("bevy-website-community-links")
+        .timeout(Duration::from_secs(20))
+        .build()?;
+
+    let now = build_manifest::now()?;
+    let (mut failures, mut skipped) = (0, 0);
+    for (url, path) in &links {
+        let host = url.host_str().unwrap_or_default();
+        let result = if is_discord_invite(url) {
+            check_discord_invite(&client, url, now, args.expiry_warning_days)
+        } else if let Some((method, check)) = social_check(url) {
+            check_request(&client, method, &check)
+        } else if allowlist.hosts.iter().any(|h| h == host) {
+            skipped += 1;
+            continue;
+        } else {
+            check_request(&client, Method::GET, url)
+        };
+        match result {
+            Ok(()) => println!("ok      {url} ({})", path.display()),
+            Err(error) => {
+                failures += 1;
+                println!("broken  {url} ({}): {error:#}", path.display());
+            }
+        }
+    }
+    println!(
+        "checked {} community links, skipped {skipped} on hosts that block automated requests",
+        links.len() - skipped
+    );
+
+    if failures > 0 {
+        println!("{failures} community links need attention");
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Literal `href`s of the templates, links built from page data are checked by `check-links`
+fn template_links(dir: &Path) -> anyhow::Result<Vec<(PathBuf, String)>> {
+    let selector = Selector::parse("a[href]").unwrap();
+    let mut links = Vec::new();
+    for path in files(dir, "html")? {
+        let html = Html::parse_fragment(
+            &fs::read_to_string(&path).with_context(|| format!("failed to read {:?}", path))?,
+        );
+        for element in html.select(&selector) {
+            let href = element.value().attr("href").unwrap();
+            if !href.contains("{{") {
+                links.push((path.clone(), href.to_string()));
+            }
+        }
+    }
+    Ok(links)
+}
+
+fn markdown_links(dirs: &[PathBuf]) -> anyhow::Result<Vec<(PathBuf, String)>> {
+    let mut links = Vec::new();
+    for dir in dirs {
+        for path in files(dir, "md")? {
+            let source =
+                fs::read_to_string(&path).with_context(|| format!("failed to read {:?}", path))?;
+            for event in Markdown::new(&source) {
+                if let Event::Start(Tag::Link { dest_url, .. }) = event {
+                    links.push((path.clone(), dest_url.to_string()));
+                }
+            }
+        }
+    }
+    Ok(links)
+}
+
+fn files(dir: &Path, extension: &str) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry.with_context(|| format!("failed to read {:?}", dir))?;
+        if entry.path().extension().is_some_and(|ext| ext == extension) {
+            files.push(entry.into_path());
+        }
+    }
+    Ok(files)
+}
+
+fn is_discord_invite(url: &Url) -> bool {
+    match url.host_str() {
+        Some("discord.gg") => true,
+        Some("discord.com" | "www.discord.com") => url.path().starts_with("/invite/"),
+        _ => false,
+    }
+}
+
+/// Social accounts are half of what this checks, so the allowlisted hosts that hold them are
+/// asked through lighter requests instead of being skipped
+fn social_check(url: &Url) -> Option<(Method, Url)> {
+    let oembed = |endpoint| Url::parse_with_params(endpoint, [("url", url.as_str())]).ok();
+    match url.host_str()? {
+        "twitter.com" | "www.twitter.com" => Some((Method::GET, oembed(TWITTER_OEMBED_URL)?)),
+        "youtu.be" => Some((Method::GET, oembed(YOUTUBE_OEMBED_URL)?)),
+        "youtube.com" | "www.youtube.com" => {
+            if url.path() == "/watch" || url.path() == "/playlist" {
+                Some((Method::GET, oembed(YOUTUBE_OEMBED_URL)?))
+            } else {
+                // Channel pages still answer 404 once the channel is gone
+                Some((Method::HEAD, url.clone()))
+            }
+        }
+        _ => None,
+    }
+}
+
+fn check_request(
+    client: &reqwest::blocking::Client,
+    method: Method,
+    url: &Url,
+) -> anyhow::Result<()> {
+    let status = client.request(method, url.clone()).send()?.status();
+    if status.is_client_error() || status.is_server_error() {
+        bail!("returned {status}");
+    }
+    Ok(())
+}
+
+/// Invite links keep redirecting after the invite is revoked, so ask the Discord API instead
+fn check_discord_invite(
+    client: &reqwest::blocking::Client,
+    url: &Url,
+    now: DateTime<Utc>,
+    expiry_warning_days: i64,
+) -> anyhow::Result<()> {
+    let Some(code) = url
+        .path_segments()
+        .and_then(|mut segments| segments.rfind(|segment| !segment.is_empty()))
+    else {
+        bail!("the invite link has no invite code");
+    };
+
+    let response = client
+        .get(format!("{DISCORD_API_URL}/invites/{code}"))
+        .query(&[("with_expiration", "true")])
+        .send()?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        bail!("the invite is invalid or has expired");
+    }
+    let invite: DiscordInvite = response
+        .error_for_status()?
+        .json()
+        .context("failed to parse the Discord invite")?;
+
+    if invite.guild.is_none() {
+        bail!("the invite doesn't point to a server");
+    }
+    if let Some(expires_at) = invite.expires_at {
+        let remaining = expires_at - now;
+        if remaining.num_days() < expiry_warning_days {
+            bail!(
+                "the invite to {} expires on {expires_at}, replace it with a permanent one",
+                invite.guild.unwrap().name
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn finds_discord_invites() {
+        assert!(is_discord_invite(&url("https://discord.gg/gMUk5Ph")));
+        assert!(is_discord_invite(&url(
+            "https://discord.com/invite/gMUk5Ph"
+        )));
+        assert!(!is_discord_invite(&url("https://discord.com/channels/1")));
+    }
+
+    #[test]
+    fn checks_twitter_through_oembed() {
+        let (method, check) = social_check(&url("https://twitter.com/BevyEngine")).unwrap();
+        assert_eq!(method, Method::GET);
+        assert_eq!(
+            check.as_str(),
+            "https://publish.twitter.com/oembed?url=https%3A%2F%2Ftwitter.com%2FBevyEngine"
+        );
+    }
+
+    #[test]
+    fn checks_youtube_videos_through_oembed_and_channels_directly() {
+        let (method, check) =
+            social_check(&url("https://www.youtube.com/watch?v=2rW7ALyHaas")).unwrap();
+        assert_eq!(method, Method::GET);
+        assert!(check.as_str().starts_with(YOUTUBE_OEMBED_URL));
+
+        let channel = url("https://www.youtube.com/c/cartdev");
+        assert_eq!(social_check(&channel), Some((Method::HEAD, channel)));
+        assert_eq!(social_check(&url("https://crates.io/crates/bevy")), None);
+    }
+}
@@ -0,0 +1,384 @@
+use clap::Parser;
+use percent_encoding::percent_decode_str;
+use scraper::{Html, Selector};
+use serde::Deserialize;
+use site_content::read_toml;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+use url::Url;
+use walkdir::WalkDir;
+
+/// Stands in for the site's origin when resolving relative links
+const LOCAL_ORIGIN: &str = "http://site.invalid/";
+
+/// Elements and attributes that point at another resource
+const LINK_ATTRIBUTES: &[(&str, &str)] = &[
+    ("a", "href"),
+    ("link", "href"),
+    ("img", "src"),
+    ("img", "srcset"),
+    ("script", "src"),
+    ("source", "src"),
+    ("source", "srcset"),
+    ("video", "src"),
+];
+
+#[derive(Parser)]
+#[command(about = "Check the links, anchors and images of the built site")]
+struct Args {
+    /// Output folder of `zola build`
+    #[arg(long, default_value = "public")]
+    public_dir: PathBuf,
+    /// Zola config used to recognize absolute links to the site itself
+    #[arg(long, default_value = "config.toml")]
+    config: PathBuf,
+    /// Hosts whose links are never fetched
+    #[arg(long, default_value = "check-links/allowlist.toml")]
+    allowlist: PathBuf,
+    /// Also request every external link
+    #[arg(long)]
+    external: bool,
+}
+
+#[derive(Deserialize)]
+struct Config {
+    base_url: String,
+}
+
+#[derive(Deserialize)]
+struct Allowlist {
+    hosts: Vec<String>,
+}
+
+/// A rendered page and everything it links to
+struct Page {
+    url: Url,
+    anchors: HashSet<String>,
+    links: Vec<String>,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let args = Args::parse();
+
+    let config: Config = read_toml(&args.config)?;
+    let base_url = Url::parse(&config.base_url)?;
+    let allowlist: Allowlist = read_toml(&args.allowlist)?;
+
+    let pages = read_pages(&args.public_dir)?;
+
+    let mut errors: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+    let mut external: BTreeMap<Url, Vec<PathBuf>> = BTreeMap::new();
+
+    for (file, page) in &pages {
+        for link in &page.links {
+            match check_link(&args.public_dir, &base_url, &pages, page, link) {
+                Ok(Some(url)) => {
+                    if !url
+                        .host_str()
+                        .is_some_and(|host| allowlist.hosts.iter().any(|h| h == host))
+                    {
+                        external.entry(url).or_default().push(file.clone());
+                    }
+                }
+                Ok(None) => {}
+                Err(error) => errors.entry(file.clone()).or_default().push(error),
+            }
+        }
+    }
+
+    if args.external {
+        let client = reqwest::blocking::Client::builder()
+            .user_agent("bevy-website-link-checker")
+            .timeout(Duration::from_secs(20))
+            .build()?;
+        for (url, files) in &external {
+            if let Err(error) = check_external(&client, url) {
+                for file in files {
+                    errors
+                        .entry(file.clone())
+                        .or_default()
+                        .push(format!("{url}: {error}"));
+                }
+            }
+        }
+    }
+
+    let checked: usize = pages.values().map(|p| p.links.len()).sum();
+    println!("checked {checked} links in {} pages", pages.len());
+
+    if errors.is_empty() {
+        return Ok(ExitCode::SUCCESS);
+    }
+    for (file, file_errors) in &errors {
+        println!("{}:", file.display());
+        for error in file_errors {
+            println!("    {error}");
+        }
+    }
+    println!(
+        "found {} broken links",
+        errors.values().map(Vec::len).sum::<usize>()
+    );
+    Ok(ExitCode::FAILURE)
+}
+
+fn read_pages(public_dir: &Path) -> anyhow::Result<HashMap<PathBuf, Page>> {
+    let id_selector = Selector::parse("[id], a[name]").unwrap();
+    let selectors: Vec<(Selector, &str)> = LINK_ATTRIBUTES
+        .iter()
+        .map(|(element, attribute)| {
+            (
+                Selector::parse(&format!("{element}[{attribute}]")).unwrap(),
+                *attribute,
+            )
+        })
+        .collect();
+
+    let mut pages = HashMap::new();
+    for entry in WalkDir::new(public_dir) {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "html") {
+            continue;
+        }
+
+        let html = Html::parse_document(&fs::read_to_string(path)?);
+        let anchors = html
+            .select(&id_selector)
+            .flat_map(|element| {
+                let element = element.value();
+                element.id().into_iter().chain(element.attr("name"))
+            })
+            .map(str::to_string)
+            .collect();
+        let links = selectors
+            .iter()
+            .flat_map(|(selector, attribute)| {
+                html.select(selector)
+                    .filter_map(|element| element.value().attr(attribute))
+                    .flat_map(|value| match *attribute {
+                        "srcset" => srcset_urls(value),
+                        _ => vec![value],
+                    })
+            })
+            .map(str::to_string)
+            .collect();
+
+        let relative = path
+            .strip_prefix(public_dir)?
+            .to_string_lossy()
+            .replace('\\', "/");
+        let url = Url::parse(LOCAL_ORIGIN)?.join(&relative)?;
+        pages.insert(
+            path.to_path_buf(),
+            Page {
+                url,
+                anchors,
+                links,
+            },
+        );
+    }
+    Ok(pages)
+}
+
+/// The URL of every candidate in a `srcset`, e.g. `boat.640w.webp 640w, boat.1280w.webp 1280w`
+fn srcset_urls(srcset: &str) -> Vec<&str> {
+    let mut urls = Vec::new();
+    let mut rest = srcset;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if rest.is_empty() {
+            return urls;
+        }
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let url = &rest[..end];
+        rest = &rest[end..];
+        // A candidate without descriptors ends at the comma right after its URL
+        match url.strip_suffix(',') {
+            Some(url) => urls.push(url.trim_end_matches(',')),
+            None => {
+                urls.push(url);
+                rest = rest.split_once(',').map_or("", |(_, rest)| rest);
+            }
+        }
+    }
+}
+
+/// Checks an internal link against the built site, or returns the url of an external link
+fn check_link(
+    public_dir: &Path,
+    base_url: &Url,
+    pages: &HashMap<PathBuf, Page>,
+    page: &Page,
+    link: &str,
+) -> Result<Option<Url>, String> {
+    let link = link.trim();
+    if link.is_empty() {
+        return Err("empty link".to_string());
+    }
+    if ["mailto:", "tel:", "javascript:", "data:"]
+        .iter()
+        .any(|scheme| link.starts_with(scheme))
+    {
+        return Ok(None);
+    }
+
+    let mut url = page
+        .url
+        .join(link)
+        .map_err(|error| format!("{link}: {error}"))?;
+    if url.host_str() == base_url.host_str() && matches!(url.scheme(), "http" | "https") {
+        let path = url.path().to_string();
+        url = Url::parse(LOCAL_ORIGIN).unwrap().join(&path).unwrap();
+        url.set_fragment(page.url.join(link).unwrap().fragment());
+    } else if url.origin() != Url::parse(LOCAL_ORIGIN).unwrap().origin() {
+        url.set_fragment(None);
+        return Ok(Some(url));
+    }
+
+    let path = percent_decode_str(url.path()).decode_utf8_lossy();
+    let mut target = public_dir.join(path.trim_start_matches('/'));
+    if target.is_dir() {
+        target.push("index.html");
+    }
+    if !target.exists() {
+        return Err(format!("{link}: {} does not exist", target.display()));
+    }
+
+    if let Some(fragment) = url.fragment().filter(|f| !f.is_empty()) {
+        let fragment = percent_decode_str(fragment).decode_utf8_lossy();
+        if let Some(target_page) = pages.get(&target) {
+            if !target_page.anchors.contains(fragment.as_ref()) {
+                return Err(format!(
+                    "{link}: no element with id `{fragment}` in {}",
+                    target.display()
+                ));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn check_external(client: &reqwest::blocking::Client, url: &Url) -> Result<(), String> {
+    let response = client
+        .head(url.clone())
+        .send()
+        .map_err(|error| error.to_string())?;
+    // Some servers don't implement HEAD, so retry those with GET before reporting them
+    let status = if response.status().is_client_error() {
+        client
+            .get(url.clone())
+            .send()
+            .map_err(|error| error.to_string())?
+            .status()
+    } else {
+        response.status()
+    };
+    if status.is_client_error() || status.is_server_error() {
+        return Err(format!("returned {status}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Written once, since the tests run in parallel
+    fn public_dir() -> &'static Path {
+        static DIR: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+        DIR.get_or_init(write_public_dir)
+    }
+
+    fn write_public_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join("check-links-tests");
+        let _ = fs::remove_dir_all(&dir);
+        for (path, contents) in [
+            ("index.html", "<a href=\"/learn/\">Learn</a>"),
+            (
+                "learn/index.html",
+                "<h2 id=\"getting-started\">Getting started</h2>",
+            ),
+            ("news/introducing-bevy/boat.png", ""),
+            ("news/introducing-bevy/boat.640w.webp", ""),
+            ("assets/press kit.zip", ""),
+        ] {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    fn check(link: &str) -> Result<Option<Url>, String> {
+        let dir = public_dir();
+        let pages = read_pages(dir).unwrap();
+        let base_url = Url::parse("https://bevyengine.org").unwrap();
+        let page = &pages[&dir.join("index.html")];
+        check_link(dir, &base_url, &pages, page, link)
+    }
+
+    #[test]
+    fn checks_internal_links() {
+        assert_eq!(check("/learn/"), Ok(None));
+        assert_eq!(check("learn"), Ok(None));
+        assert_eq!(check("/news/introducing-bevy/boat.png"), Ok(None));
+        assert!(check("/missing/").is_err());
+        assert!(check(" ").is_err());
+    }
+
+    #[test]
+    fn checks_fragments() {
+        assert_eq!(check("/learn/#getting-started"), Ok(None));
+        assert_eq!(check("/learn/#"), Ok(None));
+        assert!(check("/learn/#installation").is_err());
+    }
+
+    #[test]
+    fn decodes_percent_encoded_paths() {
+        assert_eq!(check("/assets/press%20kit.zip"), Ok(None));
+        assert!(check("/assets/press%20kits.zip").is_err());
+    }
+
+    #[test]
+    fn rewrites_links_to_the_base_url() {
+        assert_eq!(
+            check("https://bevyengine.org/learn/#getting-started"),
+            Ok(None)
+        );
+        assert!(check("https://bevyengine.org/missing/").is_err());
+        assert!(check("http://bevyengine.org/learn/#installation").is_err());
+    }
+
+    #[test]
+    fn returns_external_links_without_fragments() {
+        assert_eq!(
+            check("https://github.com/bevyengine/bevy#readme"),
+            Ok(Some(
+                Url::parse("https://github.com/bevyengine/bevy").unwrap()
+            ))
+        );
+        assert_eq!(check("mailto:hi@bevyengine.org"), Ok(None));
+    }
+
+    #[test]
+    fn reads_srcset_candidates() {
+        assert_eq!(
+            srcset_urls("boat.640w.webp 640w, boat.1280w.webp 1280w"),
+            ["boat.640w.webp", "boat.1280w.webp"]
+        );
+        assert_eq!(
+            srcset_urls(" a.webp, b.webp 2x , c.webp,"),
+            ["a.webp", "b.webp", "c.webp"]
+        );
+        // Like in browsers, a comma is only a separator after whitespace or at the end of a URL
+        assert_eq!(srcset_urls("a.webp,b.webp 2x"), ["a.webp,b.webp"]);
+        assert!(srcset_urls("").is_empty());
+    }
+}
@@ -0,0 +1,245 @@
+use clap::Parser;
+use serde::Deserialize;
+use site_content::read_toml;
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+#[derive(Parser)]
+#[command(
+    about = "Write a meta refresh page in the built site for every redirect in redirects.toml"
+)]
+struct Args {
+    #[arg(long, default_value = "redirects.toml")]
+    redirects: PathBuf,
+    /// Output folder of `zola build`, used to check that every target exists and to write the pages
+    #[arg(long, default_value = "public")]
+    public_dir: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct Redirects {
+    #[serde(default)]
+    redirect: Vec<Redirect>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Redirect {
+    from: String,
+    to: String,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let args = Args::parse();
+    let mut run = build_manifest::start!();
+    run.config(&args.redirects)?.input(&args.public_dir);
+
+    let redirects: Redirects = read_toml(&args.redirects)?;
+
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+    for redirect in &redirects.redirect {
+        if let Err(error) = validate(&args.public_dir, redirect, &mut seen) {
+            errors.push(format!("{} -> {}: {error}", redirect.from, redirect.to));
+        }
+    }
+
+    if !errors.is_empty() {
+        for error in &errors {
+            println!("{error}");
+        }
+        println!("found {} invalid redirects", errors.len());
+        return Ok(ExitCode::FAILURE);
+    }
+
+    // GitHub Pages has no server side redirects, so every old path gets a page that forwards
+    // to the new one, like the ones Zola writes for `redirect_to` and `aliases`
+    for redirect in &redirects.redirect {
+        let path = page_path(&args.public_dir, &redirect.from);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, refresh_page(&redirect.to))?;
+        run.output(&path);
+    }
+    run.finish()?;
+    println!(
+        "wrote {} redirects to {:?}",
+        redirects.redirect.len(),
+        args.public_dir
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+fn validate<'a>(
+    public_dir: &Path,
+    redirect: &'a Redirect,
+    seen: &mut HashSet<&'a str>,
+) -> Result<(), String> {
+    if !redirect.from.starts_with('/') {
+        return Err("`from` must be a path starting with `/`".to_string());
+    }
+    if redirect.from.contains(char::is_whitespace) || redirect.to.contains(char::is_whitespace) {
+        return Err("paths can't contain whitespace".to_string());
+    }
+    if redirect.from.contains(['*', '#', '?']) || redirect.to.contains(":splat") {
+        return Err("only exact paths can be redirected by a static page".to_string());
+    }
+    if !seen.insert(&redirect.from) {
+        return Err("`from` is already redirected".to_string());
+    }
+    if page_exists(public_dir, &redirect.from) {
+        return Err("`from` is an existing page, which the redirect would hide".to_string());
+    }
+
+    if redirect.to.starts_with("http://") || redirect.to.starts_with("https://") {
+        return Ok(());
+    }
+    if !redirect.to.starts_with('/') {
+        return Err("`to` must be a path starting with `/` or an external URL".to_string());
+    }
+    if !page_exists(public_dir, &redirect.to) {
+        return Err("`to` does not exist in the built site".to_string());
+    }
+    Ok(())
+}
+
+fn page_exists(public_dir: &Path, path: &str) -> bool {
+    let path = path.split(['#', '?']).next().unwrap();
+    let mut file = public_dir.join(path.trim_start_matches('/'));
+    if file.is_dir() {
+        file.push("index.html");
+    }
+    // Zola writes a meta refresh page for `redirect_to` sections, and this tool for every
+    // redirect, which are fine to replace
+    fs::read(&file)
+        .is_ok_and(|bytes| !String::from_utf8_lossy(&bytes).contains(r#"http-equiv="refresh""#))
+}
+
+/// Where the page for `from` is written. Like Zola's `aliases`, a path that doesn't name an
+/// `.html` file gets a folder with an `index.html`, since GitHub Pages serves extensionless
+/// files as downloads and redirects `/old` to `/old/`
+fn page_path(public_dir: &Path, from: &str) -> PathBuf {
+    let mut path = public_dir.join(from.trim_start_matches('/'));
+    if !from.ends_with(".html") {
+        path.push("index.html");
+    }
+    path
+}
+
+fn refresh_page(to: &str) -> String {
+    let to = to
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;");
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="utf-8">
+    <link rel="canonical" href="{to}">
+    <meta http-equiv="refresh" content="0;url={to}">
+    <title>Redirect</title>
+</head>
+<body>
+    <p><a href="{to}">Click here</a> to be redirected.</p>
+</body>
+</html>
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redirect(from: &str, to: &str) -> Redirect {
+        Redirect {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    fn public_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("compile-redirects-{name}"));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("learn/book/introduction")).unwrap();
+        fs::write(
+            dir.join("learn/book/introduction/index.html"),
+            "<p>Book</p>",
+        )
+        .unwrap();
+        fs::write(dir.join("about.html"), "<p>About</p>").unwrap();
+        fs::create_dir_all(dir.join("moved")).unwrap();
+        fs::write(dir.join("moved/index.html"), refresh_page("/learn/")).unwrap();
+        dir
+    }
+
+    fn check(dir: &Path, from: &str, to: &str) -> Result<(), String> {
+        validate(dir, &redirect(from, to), &mut HashSet::new())
+    }
+
+    #[test]
+    fn writes_folders_unless_html() {
+        let dir = Path::new("public");
+        assert_eq!(page_path(dir, "/old/"), dir.join("old/index.html"));
+        assert_eq!(page_path(dir, "/old"), dir.join("old/index.html"));
+        assert_eq!(page_path(dir, "/old.html"), dir.join("old.html"));
+    }
+
+    #[test]
+    fn finds_pages() {
+        let dir = public_dir("pages");
+        assert!(page_exists(&dir, "/learn/book/introduction/"));
+        assert!(page_exists(&dir, "/learn/book/introduction"));
+        assert!(page_exists(
+            &dir,
+            "/learn/book/introduction/#getting-started"
+        ));
+        assert!(page_exists(&dir, "/about.html?q=1"));
+        assert!(!page_exists(&dir, "/learn/book/"));
+        assert!(!page_exists(&dir, "/missing/"));
+    }
+
+    #[test]
+    fn replaces_refresh_pages() {
+        let dir = public_dir("refresh");
+        assert!(!page_exists(&dir, "/moved/"));
+        assert_eq!(check(&dir, "/moved/", "/learn/book/introduction/"), Ok(()));
+    }
+
+    #[test]
+    fn accepts_valid_redirects() {
+        let dir = public_dir("valid");
+        assert_eq!(
+            check(&dir, "/learn/book/", "/learn/book/introduction/"),
+            Ok(())
+        );
+        assert_eq!(check(&dir, "/discord", "https://discord.gg/bevy"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_invalid_redirects() {
+        let dir = public_dir("invalid");
+        let to = "/learn/book/introduction/";
+        assert!(check(&dir, "old/", to).is_err());
+        assert!(check(&dir, "/old path/", to).is_err());
+        assert!(check(&dir, "/old/*", to).is_err());
+        assert!(check(&dir, "/old/", "/new/:splat").is_err());
+        assert!(check(&dir, "/about.html", to).is_err());
+        assert!(check(&dir, "/old/", "new/").is_err());
+        assert!(check(&dir, "/old/", "/missing/").is_err());
+    }
+
+    #[test]
+    fn rejects_duplicates() {
+        let dir = public_dir("duplicates");
+        let first = redirect("/old/", "/learn/book/introduction/");
+        let second = redirect("/old/", "/learn/book/introduction/");
+        let mut seen = HashSet::new();
+        assert_eq!(validate(&dir, &first, &mut seen), Ok(()));
+        assert!(validate(&dir, &second, &mut seen).is_err());
+    }
+}
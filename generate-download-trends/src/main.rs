@@ -0,0 +1,340 @@
+use anyhow::Context;
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use site_content::read_toml;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+
+const BASE_URL: &str = "https://crates.io/api/v1";
+
+/// crates.io asks crawlers to do at most one request per second
+const REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Daily downloads per crate, keyed by `YYYY-MM-DD`
+type History = BTreeMap<String, BTreeMap<String, u64>>;
+
+#[derive(Parser)]
+#[command(about = "Compute monthly crates.io download trends for ecosystem crates")]
+struct Args {
+    #[arg(long, default_value = "generate-download-trends/crates.toml")]
+    crates: PathBuf,
+    /// crates.io only reports the last 90 days, so every run is merged into this file
+    #[arg(long, default_value = "generate-download-trends/history.json")]
+    history: PathBuf,
+    #[arg(long, default_value = "static/data/download_trends.json")]
+    output: PathBuf,
+    /// Rebuild the trends from the history without requesting crates.io
+    #[arg(long)]
+    offline: bool,
+}
+
+#[derive(Deserialize)]
+struct Crates {
+    crates: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct DownloadsResponse {
+    version_downloads: Vec<Downloads>,
+    meta: DownloadsMeta,
+}
+
+#[derive(Deserialize)]
+struct DownloadsMeta {
+    /// Downloads of versions older than the ones listed in `version_downloads`
+    #[serde(default)]
+    extra_downloads: Vec<Downloads>,
+}
+
+#[derive(Deserialize)]
+struct Downloads {
+    date: String,
+    downloads: u64,
+}
+
+/// Shaped for charting: one label per month and one series per crate
+#[derive(Serialize)]
+struct Trends {
+    months: Vec<String>,
+    /// The history starts after the first day of the first month
+    first_month_partial: bool,
+    /// The last month is still in progress when the data was fetched
+    last_month_partial: bool,
+    crates: Vec<CrateTrend>,
+}
+
+#[derive(Serialize)]
+struct CrateTrend {
+    name: String,
+    monthly_downloads: Vec<u64>,
+    /// Growth of the last complete month over the one before it, in percent. Partial months at
+    /// either end are left out.
+    growth: Option<f64>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let mut run = build_manifest::start!();
+    run.config(&args.crates)?.input(&args.history);
+
+    let crates: Crates = read_toml(&args.crates)?;
+    let mut history: History = match fs::read_to_string(&args.history) {
+        Ok(history) => serde_json::from_str(&history)
+            .with_context(|| format!("failed to parse {:?}", args.history))?,
+        Err(_) => History::new(),
+    };
+
+    if !args.offline {
+        let client = reqwest::blocking::Client::builder()
+            .user_agent("bevy-website-download-trends (https://github.com/bevyengine/bevy-website)")
+            .build()?;
+        for (i, name) in crates.crates.iter().enumerate() {
+            if i > 0 {
+                thread::sleep(REQUEST_INTERVAL);
+            }
+            let daily =
+                get_daily_downloads(&client, name).with_context(|| format!("crate {name}"))?;
+            // Newer reports replace older ones, crates.io keeps counting a day until it's over
+            history.entry(name.clone()).or_default().extend(daily);
+        }
+        fs::write(
+            &args.history,
+            serde_json::to_string_pretty(&history)? + "\n",
+        )?;
+        run.output(&args.history);
+    }
+
+    let trends = compute_trends(&crates.crates, &history)
+        .with_context(|| format!("failed to read {:?}", args.history))?;
+    if let Some(parent) = args.output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&args.output, serde_json::to_string_pretty(&trends)? + "\n")?;
+    println!(
+        "wrote {} months of downloads for {} crates to {:?}",
+        trends.months.len(),
+        trends.crates.len(),
+        args.output
+    );
+    run.output(&args.output);
+    run.finish()
+}
+
+fn get_daily_downloads(
+    client: &reqwest::blocking::Client,
+    name: &str,
+) -> anyhow::Result<BTreeMap<String, u64>> {
+    let response: DownloadsResponse = client
+        .get(format!("{BASE_URL}/crates/{name}/downloads"))
+        .send()?
+        .error_for_status()?
+        .json()?;
+
+    let mut daily = BTreeMap::new();
+    for downloads in response
+        .version_downloads
+        .iter()
+        .chain(&response.meta.extra_downloads)
+    {
+        *daily.entry(downloads.date.clone()).or_default() += downloads.downloads;
+    }
+    Ok(daily)
+}
+
+fn compute_trends(crates: &[String], history: &History) -> anyhow::Result<Trends> {
+    let mut monthly: BTreeMap<&str, BTreeMap<&str, u64>> = BTreeMap::new();
+    let (mut first_day, mut last_day) = (None, None);
+    for name in crates {
+        let months = monthly.entry(name.as_str()).or_default();
+        for (date, downloads) in history.get(name).into_iter().flatten() {
+            let day = parse_date(date)
+                .with_context(|| format!("invalid date {date:?} in the history of {name}"))?;
+            first_day = Some(first_day.map_or(day, |first: (u32, u32, u32)| first.min(day)));
+            last_day = last_day.max(Some(day));
+            *months.entry(&date[..7]).or_default() += downloads;
+        }
+    }
+
+    let all_months: BTreeSet<&str> = monthly.values().flat_map(|m| m.keys().copied()).collect();
+    let months = month_range(all_months.first(), all_months.last());
+
+    // crates.io only reports the last 90 days, so the history usually starts in the middle of a
+    // month, and the newest day with data tells whether the last month is complete
+    let first_month_partial = first_day.is_some_and(|(_, _, day)| day != 1);
+    let last_month_partial = last_day.is_some_and(|day| !is_last_day_of_month(day));
+
+    let crates = crates
+        .iter()
+        .map(|name| {
+            let monthly_downloads: Vec<u64> = months
+                .iter()
+                .map(|month| {
+                    monthly[name.as_str()]
+                        .get(month.as_str())
+                        .copied()
+                        .unwrap_or(0)
+                })
+                .collect();
+            let end = monthly_downloads.len() - usize::from(last_month_partial);
+            let start = usize::from(first_month_partial).min(end);
+            let growth = match &monthly_downloads[start..end] {
+                [.., previous, last] if *previous > 0 => {
+                    Some((*last as f64 / *previous as f64 - 1.0) * 100.0)
+                }
+                _ => None,
+            };
+            CrateTrend {
+                name: name.clone(),
+                monthly_downloads,
+                growth,
+            }
+        })
+        .collect();
+
+    Ok(Trends {
+        months,
+        first_month_partial,
+        last_month_partial,
+        crates,
+    })
+}
+
+/// Every `YYYY-MM` from `first` to `last`, so months without downloads still get a (zero) value
+fn month_range(first: Option<&&str>, last: Option<&&str>) -> Vec<String> {
+    let (Some(first), Some(last)) = (first, last) else {
+        return Vec::new();
+    };
+    let parse = |month: &str| -> (u32, u32) {
+        let (year, month) = month.split_once('-').unwrap();
+        (year.parse().unwrap(), month.parse().unwrap())
+    };
+    let (mut year, mut month) = parse(first);
+    let end = parse(last);
+
+    let mut months = Vec::new();
+    while (year, month) <= end {
+        months.push(format!("{year:04}-{month:02}"));
+        month += 1;
+        if month > 12 {
+            month = 1;
+            year += 1;
+        }
+    }
+    months
+}
+
+/// Year, month and day of a `YYYY-MM-DD` date
+fn parse_date(date: &str) -> anyhow::Result<(u32, u32, u32)> {
+    let is_date = date.len() == 10
+        && date.bytes().enumerate().all(|(i, b)| {
+            if i == 4 || i == 7 {
+                b == b'-'
+            } else {
+                b.is_ascii_digit()
+            }
+        });
+    if !is_date {
+        anyhow::bail!("expected a `YYYY-MM-DD` date");
+    }
+    let (year, month, day) = (
+        date[..4].parse().unwrap(),
+        date[5..7].parse().unwrap(),
+        date[8..].parse().unwrap(),
+    );
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        anyhow::bail!("not a valid date");
+    }
+    Ok((year, month, day))
+}
+
+fn is_last_day_of_month((year, month, day): (u32, u32, u32)) -> bool {
+    day == days_in_month(year, month)
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(days: &[(&str, u64)]) -> History {
+        let days = days
+            .iter()
+            .map(|(date, downloads)| (date.to_string(), *downloads))
+            .collect();
+        BTreeMap::from([("bevy".to_string(), days)])
+    }
+
+    fn trends(days: &[(&str, u64)]) -> Trends {
+        compute_trends(&["bevy".to_string()], &history(days)).unwrap()
+    }
+
+    #[test]
+    fn fills_months_across_years() {
+        assert_eq!(
+            month_range(Some(&"2020-11"), Some(&"2021-02")),
+            ["2020-11", "2020-12", "2021-01", "2021-02"]
+        );
+        assert!(month_range(None, None).is_empty());
+    }
+
+    #[test]
+    fn grows_between_complete_months() {
+        let trends = trends(&[("2020-09-01", 100), ("2020-10-31", 150)]);
+        assert!(!trends.first_month_partial);
+        assert!(!trends.last_month_partial);
+        assert_eq!(trends.crates[0].monthly_downloads, [100, 150]);
+        assert_eq!(trends.crates[0].growth, Some(50.0));
+    }
+
+    #[test]
+    fn leaves_out_partial_months() {
+        let trends = trends(&[
+            ("2020-08-15", 10),
+            ("2020-09-10", 100),
+            ("2020-10-05", 200),
+            ("2020-11-03", 5),
+        ]);
+        assert_eq!(trends.months, ["2020-08", "2020-09", "2020-10", "2020-11"]);
+        assert!(trends.first_month_partial);
+        assert!(trends.last_month_partial);
+        assert_eq!(trends.crates[0].growth, Some(100.0));
+    }
+
+    #[test]
+    fn needs_two_complete_months_for_growth() {
+        let one_complete = trends(&[("2020-08-15", 10), ("2020-09-30", 100)]);
+        assert!(one_complete.first_month_partial);
+        assert_eq!(one_complete.crates[0].growth, None);
+
+        let single_month = trends(&[("2020-08-15", 10)]);
+        assert!(single_month.first_month_partial && single_month.last_month_partial);
+        assert_eq!(single_month.crates[0].growth, None);
+    }
+
+    #[test]
+    fn rejects_malformed_dates() {
+        let crates = ["bevy".to_string()];
+        for date in ["2020-8", "2020-13-01", "2021-02-29", "é2020-09-01"] {
+            assert!(
+                compute_trends(&crates, &history(&[(date, 1)])).is_err(),
+                "{date}"
+            );
+        }
+        assert!(compute_trends(&crates, &history(&[("2020-02-29", 1)])).is_ok());
+    }
+}
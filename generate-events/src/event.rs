@@ -0,0 +1,149 @@
+use anyhow::{bail, Context};
+use chrono::{DateTime, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use toml::value::Datetime;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Event {
+    pub title: String,
+    pub kind: Kind,
+    pub link: String,
+    pub description: String,
+    pub location: Option<String>,
+    pub start: Datetime,
+    pub end: Option<Datetime>,
+    pub timezone: String,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Jam,
+    Meetup,
+    Talk,
+}
+
+/// An event whose times were validated and converted to UTC
+#[derive(Debug, Serialize)]
+pub struct ResolvedEvent {
+    pub title: String,
+    pub kind: Kind,
+    pub link: String,
+    pub description: String,
+    pub location: String,
+    pub timezone: String,
+    pub start: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+    /// The start as written in `events.toml`, e.g. `2020-11-20 18:00 PST`
+    pub local_start: String,
+}
+
+impl Event {
+    pub fn resolve(self) -> anyhow::Result<ResolvedEvent> {
+        if !self.link.starts_with("https://") && !self.link.starts_with("http://") {
+            bail!("`link` must be an http(s) URL");
+        }
+        let timezone: Tz = self
+            .timezone
+            .parse()
+            .map_err(|_| anyhow::anyhow!("unknown timezone {:?}", self.timezone))?;
+
+        let start = to_local(&self.start, timezone).context("invalid `start`")?;
+        let end = self
+            .end
+            .as_ref()
+            .map(|end| to_local(end, timezone).context("invalid `end`"))
+            .transpose()?;
+        if let Some(end) = end {
+            if end <= start {
+                bail!("`end` ({end}) must be after `start` ({start})");
+            }
+        }
+
+        Ok(ResolvedEvent {
+            title: self.title,
+            kind: self.kind,
+            link: self.link,
+            description: self.description,
+            location: self.location.unwrap_or_else(|| "Online".to_string()),
+            timezone: self.timezone,
+            local_start: start.format("%Y-%m-%d %H:%M %Z").to_string(),
+            start: start.with_timezone(&Utc),
+            end: end.map(|end| end.with_timezone(&Utc)),
+        })
+    }
+}
+
+impl ResolvedEvent {
+    /// Events without an end are considered over once they started
+    pub fn is_past(&self, now: DateTime<Utc>) -> bool {
+        self.end.unwrap_or(self.start) < now
+    }
+}
+
+/// Times are written without an offset and interpreted in the event's timezone,
+/// so they stay correct across daylight saving changes
+fn to_local(datetime: &Datetime, timezone: Tz) -> anyhow::Result<DateTime<Tz>> {
+    if datetime.offset.is_some() {
+        bail!("{datetime} has an offset, write the local time and set `timezone` instead");
+    }
+    let (Some(date), Some(time)) = (datetime.date, datetime.time) else {
+        bail!("{datetime} must have both a date and a time");
+    };
+    let naive = NaiveDateTime::new(
+        NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into())
+            .context("not a valid date")?,
+        NaiveTime::from_hms_opt(time.hour.into(), time.minute.into(), time.second.into())
+            .context("not a valid time")?,
+    );
+    match timezone.from_local_datetime(&naive) {
+        LocalResult::Single(local) => Ok(local),
+        LocalResult::Ambiguous(..) => {
+            bail!("{naive} happens twice in {timezone} because of a daylight saving change")
+        }
+        LocalResult::None => {
+            bail!("{naive} doesn't exist in {timezone} because of a daylight saving change")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(source: &str) -> Datetime {
+        source.parse().unwrap()
+    }
+
+    #[test]
+    fn converts_local_times_to_utc() {
+        let local = to_local(&datetime("2020-11-20T18:00:00"), Tz::America__Los_Angeles).unwrap();
+        assert_eq!(
+            local.with_timezone(&Utc).to_rfc3339(),
+            "2020-11-21T02:00:00+00:00"
+        );
+        assert_eq!(local.format("%Z").to_string(), "PST");
+    }
+
+    #[test]
+    fn rejects_ambiguous_times() {
+        // Clocks go back from 02:00 to 01:00, so 01:30 happens twice
+        let error = to_local(&datetime("2020-11-01T01:30:00"), Tz::America__New_York).unwrap_err();
+        assert!(error.to_string().contains("happens twice"), "{error}");
+    }
+
+    #[test]
+    fn rejects_nonexistent_times() {
+        // Clocks skip from 02:00 to 03:00
+        let error = to_local(&datetime("2020-03-08T02:30:00"), Tz::America__New_York).unwrap_err();
+        assert!(error.to_string().contains("doesn't exist"), "{error}");
+    }
+
+    #[test]
+    fn rejects_offsets_and_dates_without_time() {
+        assert!(to_local(&datetime("2020-11-20T18:00:00-08:00"), Tz::UTC).is_err());
+        assert!(to_local(&datetime("2020-11-20"), Tz::UTC).is_err());
+    }
+}
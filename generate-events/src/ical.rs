@@ -0,0 +1,114 @@
+use crate::event::ResolvedEvent;
+use chrono::{DateTime, Utc};
+
+const DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Lines longer than this many bytes are folded, as required by RFC 5545
+const MAX_LINE_LENGTH: usize = 75;
+
+pub fn calendar(events: &[&ResolvedEvent], now: DateTime<Utc>) -> String {
+    let mut ical = String::new();
+    line(&mut ical, "BEGIN:VCALENDAR");
+    line(&mut ical, "VERSION:2.0");
+    line(&mut ical, "PRODID:-//Bevy Engine//Community Events//EN");
+    line(&mut ical, "X-WR-CALNAME:Bevy Community Events");
+
+    for event in events {
+        line(&mut ical, "BEGIN:VEVENT");
+        line(&mut ical, &format!("UID:{}", uid(event)));
+        line(
+            &mut ical,
+            &format!("DTSTAMP:{}", now.format(DATE_TIME_FORMAT)),
+        );
+        line(
+            &mut ical,
+            &format!("DTSTART:{}", event.start.format(DATE_TIME_FORMAT)),
+        );
+        if let Some(end) = event.end {
+            line(
+                &mut ical,
+                &format!("DTEND:{}", end.format(DATE_TIME_FORMAT)),
+            );
+        }
+        line(&mut ical, &format!("SUMMARY:{}", escape(&event.title)));
+        line(
+            &mut ical,
+            &format!("DESCRIPTION:{}", escape(&event.description)),
+        );
+        line(&mut ical, &format!("LOCATION:{}", escape(&event.location)));
+        line(&mut ical, &format!("URL:{}", event.link));
+        line(&mut ical, "END:VEVENT");
+    }
+
+    line(&mut ical, "END:VCALENDAR");
+    ical
+}
+
+/// Stable across runs so calendar apps update events instead of duplicating them
+fn uid(event: &ResolvedEvent) -> String {
+    let slug = event
+        .title
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    format!("{}-{}@bevyengine.org", event.start.format("%Y%m%d"), slug)
+}
+
+fn escape(text: &str) -> String {
+    // Multi-line strings in events.toml keep CRLF when edited on Windows
+    text.replace("\r\n", "\n")
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn line(ical: &mut String, content: &str) {
+    let mut length = 0;
+    for c in content.chars() {
+        if length + c.len_utf8() > MAX_LINE_LENGTH {
+            ical.push_str("\r\n ");
+            length = 1;
+        }
+        ical.push(c);
+        length += c.len_utf8();
+    }
+    ical.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_short_lines() {
+        let mut ical = String::new();
+        line(&mut ical, "SUMMARY:Bevy Jam");
+        assert_eq!(ical, "SUMMARY:Bevy Jam\r\n");
+    }
+
+    #[test]
+    fn folds_long_lines() {
+        let mut ical = String::new();
+        line(&mut ical, &"a".repeat(100));
+        assert_eq!(
+            ical,
+            format!("{}\r\n {}\r\n", "a".repeat(75), "a".repeat(25))
+        );
+    }
+
+    #[test]
+    fn folds_between_characters() {
+        let mut ical = String::new();
+        line(&mut ical, &format!("{}é", "a".repeat(74)));
+        assert_eq!(ical, format!("{}\r\n é\r\n", "a".repeat(74)));
+        assert!(ical.split("\r\n").all(|line| line.len() <= MAX_LINE_LENGTH));
+    }
+
+    #[test]
+    fn escapes_text() {
+        assert_eq!(escape("a, b; c\\d\r\ne"), r"a\, b\; c\\d\ne");
+    }
+}
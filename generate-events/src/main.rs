@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use event::{Event, ResolvedEvent};
+use serde::{Deserialize, Serialize};
+use site_content::read_toml;
+use std::{fs, path::PathBuf, process::ExitCode};
+
+mod event;
+mod ical;
+
+#[derive(Parser)]
+#[command(about = "Generate the community events page and iCal feed from events.toml")]
+struct Args {
+    #[arg(long, default_value = "events.toml")]
+    events: PathBuf,
+    #[arg(long, default_value = "content/events")]
+    output_dir: PathBuf,
+    /// The feed is served from `/events/calendar.ics`
+    #[arg(long, default_value = "static/events/calendar.ics")]
+    calendar: PathBuf,
+    /// Splits upcoming and past events and stamps the feed, defaults to `SOURCE_DATE_EPOCH` or
+    /// the current time (RFC 3339)
+    #[arg(long)]
+    now: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+struct Events {
+    #[serde(default)]
+    event: Vec<Event>,
+}
+
+#[derive(Serialize)]
+struct FrontMatter {
+    title: &'static str,
+    template: &'static str,
+    extra: Extra,
+}
+
+#[derive(Serialize)]
+struct Extra {
+    header_message: &'static str,
+    upcoming: Vec<ResolvedEvent>,
+    past: Vec<ResolvedEvent>,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let args = Args::parse();
+    let mut run = build_manifest::start!();
+    run.config(&args.events)?;
+    let now = match args.now {
+        Some(now) => now,
+        None => build_manifest::now()?,
+    };
+
+    let events: Events = read_toml(&args.events)?;
+
+    let mut errors = Vec::new();
+    let mut resolved = Vec::new();
+    for event in events.event {
+        let title = event.title.clone();
+        match event.resolve() {
+            Ok(event) => resolved.push(event),
+            Err(error) => errors.push(format!("{title}: {error:#}")),
+        }
+    }
+    if !errors.is_empty() {
+        for error in &errors {
+            println!("{error}");
+        }
+        println!("found {} invalid events", errors.len());
+        return Ok(ExitCode::FAILURE);
+    }
+
+    resolved.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| a.title.cmp(&b.title)));
+    let (mut past, upcoming): (Vec<_>, Vec<_>) =
+        resolved.into_iter().partition(|event| event.is_past(now));
+    // The most recent past events come first on the page
+    past.reverse();
+
+    let calendar_events: Vec<&ResolvedEvent> = upcoming.iter().chain(&past).collect();
+    if let Some(parent) = args.calendar.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&args.calendar, ical::calendar(&calendar_events, now))?;
+
+    let front_matter = FrontMatter {
+        title: "Events",
+        template: "events.html",
+        extra: Extra {
+            header_message: "Events",
+            upcoming,
+            past,
+        },
+    };
+    fs::create_dir_all(&args.output_dir)?;
+    let path = args.output_dir.join("_index.md");
+    fs::write(
+        &path,
+        format!("+++\n{}+++\n", toml::to_string(&front_matter)?),
+    )?;
+    println!(
+        "wrote {} upcoming and {} past events to {:?} and {:?}",
+        front_matter.extra.upcoming.len(),
+        front_matter.extra.past.len(),
+        path,
+        args.calendar
+    );
+
+    run.output(&args.calendar).output(&path);
+    run.finish()?;
+    Ok(ExitCode::SUCCESS)
+}
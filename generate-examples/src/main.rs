@@ -0,0 +1,104 @@
+use anyhow::{bail, Context};
+use clap::Parser;
+use readme::Category;
+use serde::Serialize;
+use std::{fs, path::PathBuf, time::Duration};
+
+mod readme;
+
+const REPOSITORY: &str = "bevyengine/bevy";
+
+#[derive(Parser)]
+#[command(about = "Generate the examples section from the categories in bevy's examples/README.md")]
+struct Args {
+    /// Bevy git tag to read the examples from, which the source links point to as well
+    #[arg(long, default_value = "v0.1.3")]
+    tag: String,
+    /// Read a local copy of `examples/README.md` instead of fetching it from GitHub
+    #[arg(long)]
+    readme: Option<PathBuf>,
+    #[arg(long, default_value = "content/examples")]
+    output_dir: PathBuf,
+}
+
+#[derive(Serialize)]
+struct FrontMatter {
+    title: &'static str,
+    template: &'static str,
+    extra: Extra,
+}
+
+#[derive(Serialize)]
+struct Extra {
+    header_message: &'static str,
+    tag: String,
+    /// Base URL of the example sources at `tag`
+    source_url: String,
+    categories: Vec<Category>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let mut run = build_manifest::start!();
+
+    let readme = match &args.readme {
+        Some(path) => {
+            run.input(path);
+            fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?
+        }
+        None => fetch_readme(&args.tag)?,
+    };
+
+    let categories = readme::categories(&readme);
+    if categories.is_empty() {
+        bail!("no examples found in the README at {}", args.tag);
+    }
+
+    let front_matter = FrontMatter {
+        title: "Examples",
+        template: "examples.html",
+        extra: Extra {
+            header_message: "Examples",
+            source_url: format!(
+                "https://github.com/{REPOSITORY}/blob/{}/examples/",
+                args.tag
+            ),
+            tag: args.tag,
+            categories,
+        },
+    };
+    fs::create_dir_all(&args.output_dir)?;
+    let path = args.output_dir.join("_index.md");
+    fs::write(
+        &path,
+        format!("+++\n{}+++\n", toml::to_string(&front_matter)?),
+    )?;
+    println!(
+        "wrote {} examples in {} categories to {:?}",
+        front_matter
+            .extra
+            .categories
+            .iter()
+            .map(|category| category.examples.len())
+            .sum::<usize>(),
+        front_matter.extra.categories.len(),
+        path
+    );
+    run.output(&path);
+    run.finish()
+}
+
+fn fetch_readme(tag: &str) -> anyhow::Result<String> {
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("bevy-website-generate-examples")
+        .timeout(Duration::from_secs(30))
+        .build()?;
+    let url = format!("https://raw.githubusercontent.com/{REPOSITORY}/{tag}/examples/README.md");
+    client
+        .get(&url)
+        .send()?
+        .error_for_status()
+        .with_context(|| format!("failed to fetch {url}"))?
+        .text()
+        .map_err(Into::into)
+}
@@ -0,0 +1,134 @@
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use serde::Serialize;
+
+/// A heading of `examples/README.md` and the examples listed in its tables
+#[derive(Serialize)]
+pub struct Category {
+    pub name: String,
+    pub examples: Vec<Example>,
+}
+
+#[derive(Serialize)]
+pub struct Example {
+    pub name: String,
+    /// Relative to the `examples` folder, e.g. `2d/sprite.rs`
+    pub path: String,
+    pub description: String,
+}
+
+#[derive(Default)]
+struct Cell {
+    text: String,
+    link: Option<String>,
+}
+
+/// Reads every table row that links to a `.rs` file, grouped by the heading above it.
+/// Headings without such rows (the title, the table of contents, ...) are left out.
+pub fn categories(readme: &str) -> Vec<Category> {
+    let mut categories: Vec<Category> = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut row: Option<Vec<Cell>> = None;
+
+    for event in Parser::new_ext(readme, Options::ENABLE_TABLES) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) if level >= HeadingLevel::H2 => {
+                heading = Some(String::new());
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some(name) = heading.take() {
+                    categories.push(Category {
+                        name: name.trim().to_string(),
+                        examples: Vec::new(),
+                    });
+                }
+            }
+            Event::Start(Tag::TableRow) => row = Some(Vec::new()),
+            Event::Start(Tag::TableCell) => {
+                if let Some(row) = &mut row {
+                    row.push(Cell::default());
+                }
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                if let Some(cell) = row.as_mut().and_then(|row| row.last_mut()) {
+                    if dest_url.ends_with(".rs") {
+                        cell.link = Some(dest_url.trim_start_matches("./").to_string());
+                    }
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(heading) = &mut heading {
+                    heading.push_str(&text);
+                } else if let Some(cell) = row.as_mut().and_then(|row| row.last_mut()) {
+                    cell.text.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::TableRow) => {
+                let cells = row.take().unwrap_or_default();
+                if let (Some(path), Some(category)) = (
+                    cells.iter().find_map(|cell| cell.link.clone()),
+                    categories.last_mut(),
+                ) {
+                    category.examples.push(Example {
+                        name: cells[0].text.trim().to_string(),
+                        path,
+                        description: cells.last().unwrap().text.trim().to_string(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    categories.retain(|category| !category.examples.is_empty());
+    categories
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const README: &str = r#"# Examples
+
+These examples demonstrate the main features of Bevy and how to use them.
+
+- [Examples](#examples)
+  - [2D Rendering](#2d-rendering)
+  - [ECS (Entity Component System)](#ecs-entity-component-system)
+
+## Hello, World!
+
+Example | File | Description
+--- | --- | ---
+`hello_world` | [`hello_world.rs`](./hello_world.rs) | Runs a minimal example that outputs "hello world"
+
+## 2D Rendering
+
+Example | File | Description
+--- | --- | ---
+`contributors` | [`2d/contributors.rs`](./2d/contributors.rs) | Displays each contributor as a bouncy bevy-ball!
+`sprite` | [`2d/sprite.rs`](./2d/sprite.rs) | Renders a sprite
+
+## WASM
+
+Following is an example for `headless_wasm`. For other examples, change the `headless_wasm` name.
+"#;
+
+    #[test]
+    fn reads_examples_by_heading() {
+        let categories = categories(README);
+        let names: Vec<_> = categories.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Hello, World!", "2D Rendering"]);
+
+        let sprite = &categories[1].examples[1];
+        assert_eq!(sprite.name, "sprite");
+        assert_eq!(sprite.path, "2d/sprite.rs");
+        assert_eq!(sprite.description, "Renders a sprite");
+        assert_eq!(categories[1].examples.len(), 2);
+    }
+
+    #[test]
+    fn skips_rows_without_sources() {
+        let readme = "## Links\n\nName | Link\n--- | ---\nBook | [book](https://bevyengine.org)\n";
+        assert!(categories(readme).is_empty());
+    }
+}
@@ -0,0 +1,52 @@
+use anyhow::Context;
+use serde::Deserialize;
+use site_content::parse_front_matter;
+use std::{fs, path::Path};
+
+const SUMMARY_MARKER: &str = "<!-- more -->";
+
+#[derive(Debug, Deserialize)]
+pub struct FrontMatter {
+    pub title: String,
+    pub date: toml::value::Datetime,
+    pub slug: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Extra {
+    pub author: Option<String>,
+    pub category: Option<String>,
+}
+
+/// A news post split into its front matter and markdown body
+#[derive(Debug)]
+pub struct Post {
+    pub front_matter: FrontMatter,
+    pub body: String,
+}
+
+impl Post {
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let source =
+            fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?;
+        Self::parse(path, &source)
+    }
+
+    pub fn parse(path: &Path, source: &str) -> anyhow::Result<Self> {
+        let (front_matter, body) = parse_front_matter(path, source)?;
+        Ok(Post {
+            front_matter,
+            body: body.to_string(),
+        })
+    }
+
+    /// The markdown before `<!-- more -->`, mirroring Zola's `page.summary`
+    pub fn summary(&self) -> Option<&str> {
+        self.body
+            .split_once(SUMMARY_MARKER)
+            .map(|(summary, _)| summary.trim())
+    }
+}
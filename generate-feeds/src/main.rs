@@ -0,0 +1,265 @@
+use anyhow::{bail, Context};
+use atom_syndication::{
+    Content, Entry, EntryBuilder, Feed, FeedBuilder, FixedDateTime, LinkBuilder, PersonBuilder,
+};
+use chrono::{DateTime, NaiveDate};
+use clap::Parser;
+use front_matter::Post;
+use serde::Deserialize;
+use site_content::{
+    read_toml, replace_shortcodes, shortcode_arg, shortcode_name, strip_date_prefix,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+mod front_matter;
+
+/// A feed containing only the news posts tagged with `extra.category`
+struct CategoryFeed {
+    category: &'static str,
+    title: &'static str,
+    path: &'static str,
+}
+
+const FEEDS: &[CategoryFeed] = &[
+    CategoryFeed {
+        category: "release",
+        title: "Bevy Engine - Releases",
+        path: "news/releases/atom.xml",
+    },
+    CategoryFeed {
+        category: "community",
+        title: "Bevy Engine - Community",
+        path: "news/community/atom.xml",
+    },
+];
+
+#[derive(Parser)]
+#[command(about = "Generate category-scoped Atom feeds for the news section")]
+struct Args {
+    /// Zola config used to read the site's `base_url`
+    #[arg(long, default_value = "config.toml")]
+    config: PathBuf,
+    /// The news section of the content tree
+    #[arg(long, default_value = "content/news")]
+    news_dir: PathBuf,
+    /// Feeds are written relative to this folder so Zola copies them into the built site
+    #[arg(long, default_value = "static")]
+    output_dir: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct Config {
+    base_url: String,
+}
+
+struct NewsPost {
+    post: Post,
+    slug: String,
+    date: FixedDateTime,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let mut run = build_manifest::start!();
+    run.config(&args.config)?.input(&args.news_dir);
+
+    let config: Config = read_toml(&args.config)?;
+    let base_url = config.base_url.trim_end_matches('/');
+
+    let mut posts = read_posts(&args.news_dir)?;
+    posts.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.slug.cmp(&b.slug)));
+
+    for category_feed in FEEDS {
+        let entries: Vec<&NewsPost> = posts
+            .iter()
+            .filter(|p| {
+                p.post.front_matter.extra.category.as_deref() == Some(category_feed.category)
+            })
+            .collect();
+        let feed = build_feed(base_url, category_feed, &entries);
+
+        let path = args.output_dir.join(category_feed.path);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, feed.to_string())?;
+        run.output(&path);
+        println!("wrote {} posts to {:?}", entries.len(), path);
+    }
+
+    run.finish()
+}
+
+/// Reads every page of the news section, either `<name>/index.md` or `<name>.md`
+fn read_posts(news_dir: &Path) -> anyhow::Result<Vec<NewsPost>> {
+    let mut posts = Vec::new();
+    for entry in fs::read_dir(news_dir)? {
+        let path = entry?.path();
+        let (file, name) = if path.is_dir() {
+            (path.join("index.md"), path.file_name())
+        } else if path.extension().is_some_and(|ext| ext == "md")
+            && path.file_name().is_some_and(|name| name != "_index.md")
+        {
+            (path.clone(), path.file_stem())
+        } else {
+            continue;
+        };
+        if !file.exists() {
+            continue;
+        }
+        let name = name.unwrap().to_string_lossy().to_string();
+
+        let post = Post::from_file(&file)?;
+        let date = parse_date(&post.front_matter.date)
+            .with_context(|| format!("invalid date in {:?}", file))?;
+        let slug = post
+            .front_matter
+            .slug
+            .clone()
+            .unwrap_or_else(|| strip_date_prefix(&name).to_string());
+        posts.push(NewsPost { post, slug, date });
+    }
+    Ok(posts)
+}
+
+fn parse_date(date: &toml::value::Datetime) -> anyhow::Result<FixedDateTime> {
+    let date = date.to_string();
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(&date) {
+        return Ok(date_time);
+    }
+    match NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+        Ok(day) => Ok(day.and_hms_opt(0, 0, 0).unwrap().and_utc().fixed_offset()),
+        Err(_) => bail!("`{date}` is neither a date nor an RFC 3339 date-time"),
+    }
+}
+
+fn build_feed(base_url: &str, category_feed: &CategoryFeed, posts: &[&NewsPost]) -> Feed {
+    let feed_url = format!("{base_url}/{}", category_feed.path);
+    let entries: Vec<Entry> = posts.iter().map(|p| build_entry(base_url, p)).collect();
+    let updated = posts
+        .first()
+        .map(|p| p.date)
+        .unwrap_or_else(|| DateTime::UNIX_EPOCH.fixed_offset());
+
+    FeedBuilder::default()
+        .title(category_feed.title)
+        .id(feed_url.clone())
+        .updated(updated)
+        .links(vec![
+            LinkBuilder::default()
+                .href(feed_url)
+                .rel("self")
+                .mime_type(Some("application/atom+xml".to_string()))
+                .build(),
+            LinkBuilder::default()
+                .href(format!("{base_url}/news/"))
+                .build(),
+        ])
+        .entries(entries)
+        .build()
+}
+
+fn build_entry(base_url: &str, news_post: &NewsPost) -> Entry {
+    let front_matter = &news_post.post.front_matter;
+    let permalink = format!("{base_url}/news/{}/", news_post.slug);
+
+    let mut entry = EntryBuilder::default();
+    entry
+        .title(front_matter.title.as_str())
+        .id(permalink.clone())
+        .updated(news_post.date)
+        .published(Some(news_post.date))
+        .links(vec![LinkBuilder::default().href(permalink.clone()).build()]);
+
+    if let Some(author) = &front_matter.extra.author {
+        entry.authors(vec![PersonBuilder::default().name(author.as_str()).build()]);
+    }
+
+    let summary = news_post
+        .post
+        .summary()
+        .map(markdown_to_html)
+        .or_else(|| front_matter.description.clone());
+    if let Some(summary) = summary {
+        entry.content(Some(Content {
+            value: Some(summary),
+            content_type: Some("html".to_string()),
+            // Colocated media like `<source src="hot_reloading.mp4">` is relative to the page,
+            // which readers would otherwise resolve against the feed URL
+            base: Some(permalink),
+            ..Default::default()
+        }));
+    }
+
+    entry.build()
+}
+
+fn markdown_to_html(markdown: &str) -> String {
+    let markdown = replace_shortcodes(markdown, render_shortcode);
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, pulldown_cmark::Parser::new(&markdown));
+    html
+}
+
+/// Markdown standing in for the shortcodes used in summaries, other shortcodes are dropped
+fn render_shortcode(call: &str) -> String {
+    match shortcode_name(call) {
+        "rust_type" => shortcode_arg(call, "name")
+            .or_else(|| shortcode_arg(call, "method"))
+            .map(|name| format!("`{name}`"))
+            .unwrap_or_default(),
+        "rust_mod" => shortcode_arg(call, "mod")
+            .map(|name| format!("`{name}`"))
+            .unwrap_or_default(),
+        "picture" => match shortcode_arg(call, "src") {
+            Some(src) => format!(
+                "![{}]({src})",
+                shortcode_arg(call, "alt").unwrap_or_default()
+            ),
+            None => String::new(),
+        },
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_shortcodes_in_summaries() {
+        let markdown = "Text like {{rust_type(type=\"struct\" crate=\"bevy_render\" name=\"Texture\" no_mod=true)}}\n\n\
+            {{ picture(src=\"/news/introducing-bevy/boat.png\", alt=\"boat\") }}{{ starter_project() }}";
+        assert_eq!(
+            markdown_to_html(markdown),
+            "<p>Text like <code>Texture</code></p>\n\
+             <p><img src=\"/news/introducing-bevy/boat.png\" alt=\"boat\" /></p>\n"
+        );
+    }
+
+    #[test]
+    fn resolves_media_against_the_page() {
+        let post = Post::parse(
+            Path::new("index.md"),
+            "+++\ntitle = \"Introducing Bevy\"\ndate = 2020-08-10\n+++\n\
+             <video><source src=\"hot_reloading.mp4\"/></video>\n<!-- more -->\nRest",
+        )
+        .unwrap();
+        let news_post = NewsPost {
+            date: parse_date(&post.front_matter.date).unwrap(),
+            slug: "introducing-bevy".to_string(),
+            post,
+        };
+        let entry = build_entry("https://bevyengine.org", &news_post);
+        let content = entry.content().unwrap();
+        assert_eq!(
+            content.base(),
+            Some("https://bevyengine.org/news/introducing-bevy/")
+        );
+        assert!(content
+            .value()
+            .unwrap()
+            .contains(r#"src="hot_reloading.mp4""#));
+    }
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A game listed in `games/`, one TOML file per game.
+/// Every optional field left empty is filled in from the game's storefront when possible.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Game {
+    pub name: String,
+    pub link: String,
+    pub description: Option<String>,
+    /// A URL, or a file next to the TOML file
+    pub image: Option<String>,
+    pub author: Option<String>,
+    /// `YYYY-MM-DD`
+    pub release_date: Option<String>,
+    pub platforms: Option<Vec<Platform>>,
+}
+
+/// What a storefront knows about a game
+#[derive(Debug, Default)]
+pub struct StoreMetadata {
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub author: Option<String>,
+    pub release_date: Option<String>,
+    pub platforms: Option<Vec<Platform>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+    Windows,
+    Mac,
+    Linux,
+    Web,
+    Android,
+    Ios,
+}
+
+impl Game {
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        site_content::read_toml(path)
+    }
+
+    /// Fills every field that wasn't set manually, so the TOML file always wins
+    pub fn merge(&mut self, fetched: StoreMetadata) {
+        self.description = self.description.take().or(fetched.description);
+        self.image = self.image.take().or(fetched.image);
+        self.author = self.author.take().or(fetched.author);
+        self.release_date = self.release_date.take().or(fetched.release_date);
+        self.platforms = self.platforms.take().or(fetched.platforms);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::game::StoreMetadata;
+use anyhow::Context;
+use serde::Deserialize;
+use url::Url;
+
+/// The public `data.json` every itch.io game page exposes
+#[derive(Deserialize)]
+struct GameData {
+    cover_image: Option<String>,
+    #[serde(default)]
+    authors: Vec<Author>,
+}
+
+#[derive(Deserialize)]
+struct Author {
+    name: String,
+}
+
+pub struct ItchClient {
+    agent: reqwest::blocking::Client,
+}
+
+impl ItchClient {
+    pub fn new(agent: reqwest::blocking::Client) -> Self {
+        Self { agent }
+    }
+
+    /// Game pages live at `<author>.itch.io/<game>`
+    pub fn is_game_page(url: &Url) -> bool {
+        url.host_str()
+            .is_some_and(|host| host.ends_with(".itch.io"))
+            && url
+                .path_segments()
+                .is_some_and(|mut s| s.next().is_some_and(|s| !s.is_empty()))
+    }
+
+    pub fn get_metadata(&self, url: &Url) -> anyhow::Result<StoreMetadata> {
+        let data_url = format!("{}/data.json", url.as_str().trim_end_matches('/'));
+        let data: GameData = self
+            .agent
+            .get(data_url)
+            .send()?
+            .error_for_status()?
+            .json()
+            .context("failed to parse itch.io game data")?;
+
+        Ok(StoreMetadata {
+            image: data.cover_image,
+            author: data.authors.into_iter().next().map(|author| author.name),
+            ..Default::default()
+        })
+    }
+}
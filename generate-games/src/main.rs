@@ -0,0 +1,172 @@
+use anyhow::{bail, Context};
+use chrono::NaiveDate;
+use clap::Parser;
+use game::Game;
+use itch_client::ItchClient;
+use serde::Serialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use steam_client::SteamClient;
+use url::Url;
+
+mod game;
+mod itch_client;
+mod steam_client;
+
+#[derive(Parser)]
+#[command(about = "Generate the \"Games made with Bevy\" section from games/")]
+struct Args {
+    /// Folder containing one TOML file per game
+    #[arg(long, default_value = "games")]
+    games_dir: PathBuf,
+    #[arg(long, default_value = "content/games")]
+    output_dir: PathBuf,
+    /// Only use the fields written in the TOML files
+    #[arg(long)]
+    offline: bool,
+}
+
+#[derive(Serialize)]
+struct FrontMatter {
+    title: &'static str,
+    template: &'static str,
+    extra: Extra,
+}
+
+#[derive(Serialize)]
+struct Extra {
+    header_message: &'static str,
+    games: Vec<Game>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let mut run = build_manifest::start!();
+    run.input(&args.games_dir);
+
+    let agent = reqwest::blocking::Client::builder()
+        .user_agent("bevy-website-generate-games")
+        .timeout(Duration::from_secs(30))
+        .build()?;
+    let steam = SteamClient::new(agent.clone());
+    let itch = ItchClient::new(agent);
+
+    fs::create_dir_all(&args.output_dir)?;
+
+    let mut games = Vec::new();
+    for path in toml_files(&args.games_dir)? {
+        let mut game = Game::from_file(&path)?;
+
+        if !args.offline {
+            let url =
+                Url::parse(&game.link).with_context(|| format!("invalid link in {:?}", path))?;
+            let fetched = if let Some(app_id) = SteamClient::app_id(&url)
+                .filter(|_| url.host_str() == Some("store.steampowered.com"))
+            {
+                Some(steam.get_metadata(app_id))
+            } else if ItchClient::is_game_page(&url) {
+                Some(itch.get_metadata(&url))
+            } else {
+                None
+            };
+            match fetched {
+                Some(Ok(metadata)) => game.merge(metadata),
+                Some(Err(error)) => {
+                    eprintln!(
+                        "failed to get storefront metadata for {}: {error:#}",
+                        game.name
+                    )
+                }
+                None => {}
+            }
+        }
+
+        validate(&game).with_context(|| format!("invalid game {:?}", path))?;
+        if let Some(image) = &game.image {
+            if !is_url(image) {
+                copy_image(&args.games_dir, &args.output_dir, image)
+                    .with_context(|| format!("invalid image in {:?}", path))?;
+            }
+        }
+        games.push(game);
+    }
+
+    // Newest games first, unreleased or undated ones at the end
+    games.sort_by(|a, b| {
+        b.release_date
+            .cmp(&a.release_date)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    let front_matter = FrontMatter {
+        title: "Games",
+        template: "games.html",
+        extra: Extra {
+            header_message: "Games",
+            games,
+        },
+    };
+    let path = args.output_dir.join("_index.md");
+    fs::write(
+        &path,
+        format!("+++\n{}+++\n", toml::to_string(&front_matter)?),
+    )?;
+    println!(
+        "wrote {} games to {:?}",
+        front_matter.extra.games.len(),
+        path
+    );
+
+    // Copied images are written next to `_index.md`
+    run.output(&args.output_dir);
+    run.finish()
+}
+
+fn toml_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read {:?}", dir))? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn validate(game: &Game) -> anyhow::Result<()> {
+    if !is_url(&game.link) {
+        bail!("`link` must be an http(s) URL");
+    }
+    if let Some(date) = &game.release_date {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .with_context(|| format!("`release_date` {date:?} is not a YYYY-MM-DD date"))?;
+    }
+    Ok(())
+}
+
+fn is_url(value: &str) -> bool {
+    value.starts_with("https://") || value.starts_with("http://")
+}
+
+/// Local images are copied next to the generated section so Zola serves them as colocated assets
+fn copy_image(games_dir: &Path, output_dir: &Path, image: &str) -> anyhow::Result<()> {
+    if Path::new(image)
+        .file_name()
+        .is_none_or(|name| name != image)
+    {
+        bail!(
+            "local images must be a file name in {:?}, got {image:?}",
+            games_dir
+        );
+    }
+    let source = games_dir.join(image);
+    if !source.is_file() {
+        bail!("{:?} does not exist", source);
+    }
+    fs::copy(&source, output_dir.join(image))?;
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use crate::game::{Platform, StoreMetadata};
+use anyhow::{bail, Context};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::HashMap;
+use url::Url;
+
+const BASE_URL: &str = "https://store.steampowered.com/api";
+
+#[derive(Deserialize)]
+struct AppDetailsResponse {
+    success: bool,
+    data: Option<AppDetails>,
+}
+
+#[derive(Deserialize)]
+struct AppDetails {
+    short_description: Option<String>,
+    header_image: Option<String>,
+    #[serde(default)]
+    developers: Vec<String>,
+    release_date: Option<ReleaseDate>,
+    platforms: Option<Platforms>,
+}
+
+#[derive(Deserialize)]
+struct ReleaseDate {
+    coming_soon: bool,
+    date: String,
+}
+
+#[derive(Deserialize)]
+struct Platforms {
+    windows: bool,
+    mac: bool,
+    linux: bool,
+}
+
+pub struct SteamClient {
+    agent: reqwest::blocking::Client,
+}
+
+impl SteamClient {
+    pub fn new(agent: reqwest::blocking::Client) -> Self {
+        Self { agent }
+    }
+
+    /// Returns the app id of a `store.steampowered.com/app/<id>/...` link
+    pub fn app_id(url: &Url) -> Option<u32> {
+        let mut segments = url.path_segments()?;
+        match (segments.next(), segments.next()) {
+            (Some("app"), Some(id)) => id.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn get_metadata(&self, app_id: u32) -> anyhow::Result<StoreMetadata> {
+        let mut response: HashMap<String, AppDetailsResponse> = self
+            .agent
+            .get(format!("{BASE_URL}/appdetails"))
+            .query(&[("appids", app_id.to_string()), ("l", "english".to_string())])
+            .send()?
+            .error_for_status()?
+            .json()
+            .context("failed to parse Steam app details")?;
+
+        let Some(AppDetailsResponse {
+            success: true,
+            data: Some(details),
+        }) = response.remove(&app_id.to_string())
+        else {
+            bail!("Steam has no details for app {app_id}");
+        };
+
+        let release_date = details
+            .release_date
+            .filter(|date| !date.coming_soon)
+            .and_then(|date| parse_release_date(&date.date));
+        let platforms = details.platforms.map(|platforms| {
+            [
+                (platforms.windows, Platform::Windows),
+                (platforms.mac, Platform::Mac),
+                (platforms.linux, Platform::Linux),
+            ]
+            .into_iter()
+            .filter_map(|(supported, platform)| supported.then_some(platform))
+            .collect()
+        });
+
+        Ok(StoreMetadata {
+            description: details.short_description,
+            image: details.header_image,
+            author: details.developers.into_iter().next(),
+            release_date,
+            platforms,
+        })
+    }
+}
+
+/// Steam formats dates for display, e.g. `1 Dec, 2020` or `Dec 1, 2020`
+fn parse_release_date(date: &str) -> Option<String> {
+    ["%e %b, %Y", "%b %e, %Y", "%e %B, %Y", "%B %e, %Y"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(date.trim(), format).ok())
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
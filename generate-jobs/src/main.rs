@@ -0,0 +1,140 @@
+use anyhow::Context;
+use chrono::NaiveDate;
+use clap::Parser;
+use posting::Posting;
+use serde::Serialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+mod posting;
+
+#[derive(Parser)]
+#[command(about = "Generate the jobs and bounties board from jobs/")]
+struct Args {
+    /// Folder containing one TOML file per posting
+    #[arg(long, default_value = "jobs")]
+    jobs_dir: PathBuf,
+    #[arg(long, default_value = "content/jobs")]
+    output_dir: PathBuf,
+    /// Delete the TOML files of expired postings
+    #[arg(long)]
+    remove_expired: bool,
+    /// Date used to decide which postings are expired, defaults to today in UTC, or the day of
+    /// `SOURCE_DATE_EPOCH` (YYYY-MM-DD)
+    #[arg(long)]
+    today: Option<NaiveDate>,
+}
+
+#[derive(Serialize)]
+struct FrontMatter {
+    title: &'static str,
+    template: &'static str,
+    extra: Extra,
+}
+
+#[derive(Serialize)]
+struct Extra {
+    header_message: &'static str,
+    postings: Vec<Posting>,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let args = Args::parse();
+    let mut run = build_manifest::start!();
+    run.input(&args.jobs_dir);
+    let today = match args.today {
+        Some(today) => today,
+        None => build_manifest::now()?.date_naive(),
+    };
+
+    let mut errors = Vec::new();
+    let mut postings = Vec::new();
+    let mut expired = Vec::new();
+    for path in toml_files(&args.jobs_dir)? {
+        let posting = match Posting::from_file(&path) {
+            Ok(posting) => posting,
+            Err(error) => {
+                errors.push(format!("{error:#}"));
+                continue;
+            }
+        };
+        if let Err(error) = posting.validate() {
+            errors.push(format!("{}: {error:#}", path.display()));
+            continue;
+        }
+
+        if posting.is_expired(today)? {
+            expired.push(path);
+        } else {
+            postings.push(posting);
+        }
+    }
+
+    // Nothing is deleted until every posting is valid, so a failed run leaves `jobs/` as it was
+    if !errors.is_empty() {
+        for error in &errors {
+            println!("{error}");
+        }
+        println!("found {} invalid postings", errors.len());
+        return Ok(ExitCode::FAILURE);
+    }
+
+    for path in &expired {
+        if args.remove_expired {
+            fs::remove_file(path)?;
+            println!("removed expired posting {}", path.display());
+        } else {
+            println!(
+                "skipping expired posting {}, run with --remove-expired to delete it",
+                path.display()
+            );
+        }
+    }
+
+    // Newest postings first
+    postings.sort_by(|a, b| {
+        b.posted_date()
+            .ok()
+            .cmp(&a.posted_date().ok())
+            .then_with(|| a.title.cmp(&b.title))
+    });
+
+    let front_matter = FrontMatter {
+        title: "Jobs",
+        template: "jobs.html",
+        extra: Extra {
+            header_message: "Jobs",
+            postings,
+        },
+    };
+    fs::create_dir_all(&args.output_dir)?;
+    let path = args.output_dir.join("_index.md");
+    fs::write(
+        &path,
+        format!("+++\n{}+++\n", toml::to_string(&front_matter)?),
+    )?;
+    println!(
+        "wrote {} postings to {:?}",
+        front_matter.extra.postings.len(),
+        path
+    );
+
+    run.output(&path);
+    run.finish()?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn toml_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read {:?}", dir))? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
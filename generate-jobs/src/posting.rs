@@ -0,0 +1,135 @@
+use anyhow::{bail, Context};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use toml::value::Datetime;
+
+/// Postings can't stay up longer than this without being renewed
+pub const MAX_LIFETIME_DAYS: i64 = 180;
+
+/// A job or bounty listed in `jobs/`, one TOML file per posting
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Posting {
+    pub title: String,
+    pub kind: Kind,
+    pub organization: String,
+    pub link: String,
+    pub description: String,
+    pub compensation: Option<String>,
+    pub posted: Datetime,
+    pub expires: Datetime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Job,
+    Bounty,
+}
+
+impl Posting {
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        site_content::read_toml(path)
+    }
+
+    pub fn posted_date(&self) -> anyhow::Result<NaiveDate> {
+        to_date(&self.posted).context("invalid `posted`")
+    }
+
+    pub fn expires_date(&self) -> anyhow::Result<NaiveDate> {
+        to_date(&self.expires).context("invalid `expires`")
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !self.link.starts_with("https://") && !self.link.starts_with("http://") {
+            bail!("`link` must be an http(s) URL");
+        }
+        let (posted, expires) = (self.posted_date()?, self.expires_date()?);
+        if expires <= posted {
+            bail!("`expires` ({expires}) must be after `posted` ({posted})");
+        }
+        if (expires - posted).num_days() > MAX_LIFETIME_DAYS {
+            bail!("postings can't stay up longer than {MAX_LIFETIME_DAYS} days, `expires` is {expires}");
+        }
+        Ok(())
+    }
+
+    pub fn is_expired(&self, today: NaiveDate) -> anyhow::Result<bool> {
+        Ok(self.expires_date()? < today)
+    }
+}
+
+/// Only plain dates are accepted, e.g. `expires = 2020-12-01`
+fn to_date(datetime: &Datetime) -> anyhow::Result<NaiveDate> {
+    match (datetime.date, datetime.time) {
+        (Some(date), None) => {
+            NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into())
+                .context("not a valid date")
+        }
+        _ => bail!("expected a date like 2020-12-01, got {datetime}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn posting(posted: &str, expires: &str) -> Posting {
+        toml::from_str(&format!(
+            r#"
+            title = "Rendering engineer"
+            kind = "job"
+            organization = "Bevy"
+            link = "https://bevyengine.org"
+            description = "Work on the renderer"
+            posted = {posted}
+            expires = {expires}
+            "#
+        ))
+        .unwrap()
+    }
+
+    fn date(date: &str) -> NaiveDate {
+        date.parse().unwrap()
+    }
+
+    #[test]
+    fn accepts_valid_postings() {
+        assert!(posting("2020-09-01", "2020-10-01").validate().is_ok());
+        assert!(posting("2020-09-01", "2021-02-28").validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_postings() {
+        assert!(posting("2020-09-01", "2020-09-01").validate().is_err());
+        assert!(posting("2020-09-01", "2020-08-01").validate().is_err());
+        assert!(posting("2020-09-01", "2021-03-01").validate().is_err());
+
+        let mut posting = posting("2020-09-01", "2020-10-01");
+        posting.link = "bevyengine.org".to_string();
+        assert!(posting.validate().is_err());
+    }
+
+    #[test]
+    fn expires_after_the_last_day() {
+        let posting = posting("2020-09-01", "2020-10-01");
+        assert!(!posting.is_expired(date("2020-09-30")).unwrap());
+        assert!(!posting.is_expired(date("2020-10-01")).unwrap());
+        assert!(posting.is_expired(date("2020-10-02")).unwrap());
+    }
+
+    #[test]
+    fn accepts_only_plain_dates() {
+        assert_eq!(
+            to_date(&"2020-12-01".parse().unwrap()).unwrap(),
+            date("2020-12-01")
+        );
+        assert!(to_date(&"2020-12-01T10:00:00Z".parse().unwrap()).is_err());
+        assert!(to_date(&"2020-12-01T10:00:00".parse().unwrap()).is_err());
+        assert!(to_date(&"10:00:00".parse().unwrap()).is_err());
+        assert!(posting("2020-09-01T10:00:00Z", "2020-10-01")
+            .validate()
+            .is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use anyhow::{bail, Context};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use site_content::read_toml;
+use std::{fmt::Write, fs, path::PathBuf};
+use url::Url;
+
+/// Browsers truncate or reject longer names, see the OpenSearch 1.1 specification
+const MAX_SHORT_NAME_LENGTH: usize = 16;
+const MAX_DESCRIPTION_LENGTH: usize = 1024;
+
+/// Icon shown next to the engines in the browser, relative to the static folder
+const FAVICON: &str = "assets/favicon.png";
+
+#[derive(Parser)]
+#[command(about = "Generate OpenSearch descriptors from the search configuration in config.toml")]
+struct Args {
+    #[arg(long, default_value = "config.toml")]
+    config: PathBuf,
+    /// Used to check that every scoped engine filters on an existing section
+    #[arg(long, default_value = "content")]
+    content_dir: PathBuf,
+    /// Descriptors and `search_templates.json` are served from the root of the site
+    #[arg(long, default_value = "static")]
+    output_dir: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct Config {
+    base_url: String,
+    extra: ConfigExtra,
+}
+
+#[derive(Deserialize)]
+struct ConfigExtra {
+    search: Search,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Search {
+    /// Page that runs the query, e.g. `/search/`
+    path: String,
+    query_parameter: String,
+    section_parameter: String,
+    engines: Vec<Engine>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Engine {
+    file: String,
+    short_name: String,
+    description: String,
+    /// Limits results to a top level section of the site
+    section: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Template<'a> {
+    short_name: &'a str,
+    descriptor: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    section: Option<&'a str>,
+    /// The query URL with `{searchTerms}` in place of the query
+    template: String,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let mut run = build_manifest::start!();
+    run.config(&args.config)?;
+
+    let config: Config = read_toml(&args.config)?;
+    let base_url = Url::parse(&config.base_url).context("invalid base_url")?;
+    let search = &config.extra.search;
+    let search_page = base_url.join(&search.path)?;
+
+    // Browsers pick the icon by its declared size, so it must match the file
+    let favicon = args.output_dir.join(FAVICON);
+    let favicon_size = image::image_dimensions(&favicon)
+        .with_context(|| format!("failed to read {:?}", favicon))?;
+    run.input(&favicon);
+
+    let mut templates = Vec::new();
+    fs::create_dir_all(&args.output_dir)?;
+    for engine in &search.engines {
+        validate(engine, &args).with_context(|| format!("invalid engine {:?}", engine.file))?;
+
+        let template = Template {
+            short_name: &engine.short_name,
+            descriptor: base_url.join(&engine.file)?.to_string(),
+            section: engine.section.as_deref(),
+            template: query_template(&search_page, search, engine),
+        };
+        let path = args.output_dir.join(&engine.file);
+        fs::write(
+            &path,
+            descriptor(engine, &template, &base_url, &search_page, favicon_size)?,
+        )?;
+        run.output(path);
+        templates.push(template);
+    }
+
+    let path = args.output_dir.join("search_templates.json");
+    fs::write(&path, serde_json::to_string_pretty(&templates)? + "\n")?;
+    run.output(path);
+    println!(
+        "wrote {} OpenSearch descriptors to {:?}",
+        templates.len(),
+        args.output_dir
+    );
+    run.finish()
+}
+
+fn validate(engine: &Engine, args: &Args) -> anyhow::Result<()> {
+    if !engine.file.ends_with(".xml") || engine.file.contains('/') {
+        bail!("descriptors must be `.xml` files at the root of the site");
+    }
+    if engine.short_name.chars().count() > MAX_SHORT_NAME_LENGTH {
+        bail!("short_name is longer than {MAX_SHORT_NAME_LENGTH} characters");
+    }
+    if engine.description.chars().count() > MAX_DESCRIPTION_LENGTH {
+        bail!("description is longer than {MAX_DESCRIPTION_LENGTH} characters");
+    }
+    if let Some(section) = &engine.section {
+        let dir = args.content_dir.join(section);
+        if !dir.join("_index.md").exists() && !dir.join("index.md").exists() {
+            bail!(
+                "section {section:?} doesn't exist in {:?}",
+                args.content_dir
+            );
+        }
+    }
+    Ok(())
+}
+
+fn query_template(search_page: &Url, search: &Search, engine: &Engine) -> String {
+    // Built by hand because `Url` would percent-encode the `{searchTerms}` placeholder
+    let mut template = format!("{search_page}?{}={{searchTerms}}", search.query_parameter);
+    if let Some(section) = &engine.section {
+        template.push_str(&format!("&{}={section}", search.section_parameter));
+    }
+    template
+}
+
+fn descriptor(
+    engine: &Engine,
+    template: &Template,
+    base_url: &Url,
+    search_page: &Url,
+    (favicon_width, favicon_height): (u32, u32),
+) -> anyhow::Result<String> {
+    let mut xml = String::new();
+    writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        xml,
+        r#"<OpenSearchDescription xmlns="http://a9.com/-/spec/opensearch/1.1/" xmlns:moz="http://www.mozilla.org/2006/browser/search/">"#
+    )?;
+    writeln!(
+        xml,
+        "  <ShortName>{}</ShortName>",
+        escape(&engine.short_name)
+    )?;
+    writeln!(
+        xml,
+        "  <Description>{}</Description>",
+        escape(&engine.description)
+    )?;
+    writeln!(xml, "  <InputEncoding>UTF-8</InputEncoding>")?;
+    writeln!(
+        xml,
+        r#"  <Image width="{favicon_width}" height="{favicon_height}" type="image/png">{}</Image>"#,
+        base_url.join(FAVICON)?
+    )?;
+    writeln!(
+        xml,
+        r#"  <Url type="text/html" method="get" template="{}"/>"#,
+        escape(&template.template)
+    )?;
+    writeln!(
+        xml,
+        r#"  <Url type="application/opensearchdescription+xml" rel="self" template="{}"/>"#,
+        escape(&template.descriptor)
+    )?;
+    writeln!(xml, "  <moz:SearchForm>{search_page}</moz:SearchForm>")?;
+    writeln!(xml, "</OpenSearchDescription>")?;
+    Ok(xml)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,178 @@
+use anyhow::{bail, Context};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use serde_json::Number;
+use sha2::{Digest, Sha256};
+use site_content::read_toml;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+#[derive(Parser)]
+#[command(about = "Generate the press kit manifest of Bevy's logo and branding files")]
+struct Args {
+    #[arg(long, default_value = "generate-press-kit/press_kit.toml")]
+    config: PathBuf,
+    #[arg(long, default_value = "static")]
+    static_dir: PathBuf,
+    #[arg(long, default_value = "static/press_kit.json")]
+    manifest: PathBuf,
+    /// Fail if the committed manifest doesn't match the files instead of rewriting it
+    #[arg(long)]
+    check: bool,
+}
+
+#[derive(Deserialize)]
+struct Config {
+    asset: Vec<AssetConfig>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AssetConfig {
+    file: String,
+    description: String,
+    license: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Manifest {
+    assets: Vec<Asset>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Asset {
+    /// URL path of the file on the site
+    path: String,
+    description: String,
+    license: String,
+    format: String,
+    /// Whole pixels for raster images, user units of the `viewBox` for SVGs
+    width: Number,
+    height: Number,
+    bytes: u64,
+    sha256: String,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let args = Args::parse();
+    let mut run = build_manifest::start!();
+    run.config(&args.config)?;
+
+    let config: Config = read_toml(&args.config)?;
+
+    let assets = config
+        .asset
+        .into_iter()
+        .map(|asset| {
+            let path = args.static_dir.join(&asset.file);
+            run.input(&path);
+            describe(&path, asset).with_context(|| format!("failed to read {:?}", path))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let manifest = Manifest { assets };
+
+    if !args.check {
+        fs::write(
+            &args.manifest,
+            serde_json::to_string_pretty(&manifest)? + "\n",
+        )?;
+        run.output(&args.manifest);
+        run.finish()?;
+        println!(
+            "wrote {} assets to {:?}",
+            manifest.assets.len(),
+            args.manifest
+        );
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let committed: Manifest = serde_json::from_str(
+        &fs::read_to_string(&args.manifest)
+            .with_context(|| format!("failed to read {:?}", args.manifest))?,
+    )?;
+    let mut changed = false;
+    for asset in &manifest.assets {
+        match committed.assets.iter().find(|a| a.path == asset.path) {
+            Some(previous) if previous == asset => {}
+            Some(previous) if previous.sha256 != asset.sha256 => {
+                changed = true;
+                println!("{} was modified", asset.path);
+            }
+            Some(_) => {
+                changed = true;
+                println!("{} has outdated metadata", asset.path);
+            }
+            None => {
+                changed = true;
+                println!("{} is missing from the manifest", asset.path);
+            }
+        }
+    }
+    for asset in &committed.assets {
+        if !manifest.assets.iter().any(|a| a.path == asset.path) {
+            changed = true;
+            println!("{} is no longer part of the press kit", asset.path);
+        }
+    }
+
+    if changed {
+        println!(
+            "branding files don't match {:?}, run `cargo run -p generate-press-kit` if the change is intended",
+            args.manifest
+        );
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn describe(path: &Path, config: AssetConfig) -> anyhow::Result<Asset> {
+    let bytes = fs::read(path)?;
+    let format = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    let (width, height) = match format.as_str() {
+        "svg" => {
+            let (width, height) = svg_size(std::str::from_utf8(&bytes)?)?;
+            let number = |value| Number::from_f64(value).context("viewBox sizes must be finite");
+            (number(width)?, number(height)?)
+        }
+        _ => {
+            let (width, height) = image::image_dimensions(path)?;
+            (width.into(), height.into())
+        }
+    };
+
+    Ok(Asset {
+        path: format!("/{}", config.file),
+        description: config.description,
+        license: config.license,
+        format,
+        width,
+        height,
+        bytes: bytes.len() as u64,
+        sha256: format!("{:x}", Sha256::digest(&bytes)),
+    })
+}
+
+/// SVG sizes come from the `viewBox`, since `width` and `height` often use physical units
+fn svg_size(svg: &str) -> anyhow::Result<(f64, f64)> {
+    let document = roxmltree::Document::parse(svg)?;
+    let root = document.root_element();
+    let Some(view_box) = root.attribute("viewBox") else {
+        bail!("SVG has no viewBox");
+    };
+    let values: Vec<f64> = view_box
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|v| !v.is_empty())
+        .map(str::parse)
+        .collect::<Result<_, _>>()?;
+    match values[..] {
+        [_, _, width, height] => Ok((width, height)),
+        _ => bail!("invalid viewBox {view_box:?}"),
+    }
+}
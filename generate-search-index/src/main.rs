@@ -0,0 +1,173 @@
+use clap::Parser;
+use flate2::{write::GzEncoder, Compression};
+use scraper::{ElementRef, Html, Selector};
+use serde::Serialize;
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+use walkdir::WalkDir;
+
+/// Bumped whenever the shape of the index changes so the search boxes can detect stale caches
+const INDEX_VERSION: u32 = 1;
+
+/// Number of characters kept from the start of each page's text
+const EXCERPT_LENGTH: usize = 200;
+
+#[derive(Parser)]
+#[command(about = "Build the compressed client-side search index from the built site")]
+struct Args {
+    /// Output folder of `zola build`
+    #[arg(long, default_value = "public")]
+    public_dir: PathBuf,
+    /// Where to write the gzipped index, defaults to `search_index.json.gz` in the public folder
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct SearchIndex {
+    version: u32,
+    documents: Vec<Document>,
+}
+
+#[derive(Serialize)]
+struct Document {
+    url: String,
+    /// Top level section of the site (`learn`, `news`, ...), used by each search box to filter results
+    section: String,
+    title: String,
+    headings: Vec<Heading>,
+    excerpt: String,
+}
+
+#[derive(Serialize)]
+struct Heading {
+    text: String,
+    anchor: Option<String>,
+}
+
+struct Selectors {
+    redirect: Selector,
+    page_title: Selector,
+    title: Selector,
+    content: Selector,
+    headings: Selector,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let mut run = build_manifest::start!();
+    run.input(&args.public_dir);
+    let selectors = Selectors {
+        redirect: Selector::parse(r#"meta[http-equiv="refresh"]"#).unwrap(),
+        page_title: Selector::parse("h1").unwrap(),
+        title: Selector::parse("title").unwrap(),
+        content: Selector::parse("section.section").unwrap(),
+        headings: Selector::parse("h1, h2, h3").unwrap(),
+    };
+
+    let mut documents = Vec::new();
+    for entry in WalkDir::new(&args.public_dir).sort_by_file_name() {
+        let entry = entry?;
+        let path = entry.path();
+        if path.file_name().is_none_or(|name| name != "index.html") {
+            continue;
+        }
+        let html = Html::parse_document(&fs::read_to_string(path)?);
+        if let Some(document) = index_page(&args.public_dir, path, &html, &selectors)? {
+            documents.push(document);
+        }
+    }
+
+    let output = args
+        .output
+        .unwrap_or_else(|| args.public_dir.join("search_index.json.gz"));
+    let mut encoder = GzEncoder::new(File::create(&output)?, Compression::best());
+    serde_json::to_writer(
+        &mut encoder,
+        &SearchIndex {
+            version: INDEX_VERSION,
+            documents,
+        },
+    )?;
+    encoder.finish()?.flush()?;
+
+    run.output(&output);
+    println!("wrote {:?}", output);
+    run.finish()
+}
+
+fn index_page(
+    public_dir: &Path,
+    path: &Path,
+    html: &Html,
+    selectors: &Selectors,
+) -> anyhow::Result<Option<Document>> {
+    // Zola writes a page containing only a refresh for every `redirect_to` and alias
+    if html.select(&selectors.redirect).next().is_some() {
+        return Ok(None);
+    }
+    let Some(content) = html.select(&selectors.content).next() else {
+        return Ok(None);
+    };
+
+    let relative = path
+        .parent()
+        .unwrap()
+        .strip_prefix(public_dir)?
+        .to_string_lossy()
+        .replace('\\', "/");
+    let url = if relative.is_empty() {
+        "/".to_string()
+    } else {
+        format!("/{relative}/")
+    };
+    let section = relative.split('/').next().unwrap_or_default().to_string();
+
+    // Prefer the page's own heading over the `Bevy - ` prefixed document title
+    let title = content
+        .select(&selectors.page_title)
+        .chain(html.select(&selectors.title))
+        .map(text_of)
+        .find(|title| !title.is_empty())
+        .map(|title| title.trim_start_matches("Bevy - ").to_string())
+        .unwrap_or_default();
+    let headings = content
+        .select(&selectors.headings)
+        .map(|heading| Heading {
+            text: text_of(heading),
+            anchor: heading.value().id().map(str::to_string),
+        })
+        .filter(|heading| !heading.text.is_empty() && heading.text != title)
+        .collect();
+
+    Ok(Some(Document {
+        url,
+        section,
+        title,
+        headings,
+        excerpt: excerpt(&text_of(content)),
+    }))
+}
+
+/// The element's text with whitespace collapsed
+fn text_of(element: ElementRef) -> String {
+    element
+        .text()
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn excerpt(text: &str) -> String {
+    if text.chars().count() <= EXCERPT_LENGTH {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(EXCERPT_LENGTH).collect();
+    match truncated.rsplit_once(' ') {
+        Some((words, _)) => format!("{words}…"),
+        None => format!("{truncated}…"),
+    }
+}
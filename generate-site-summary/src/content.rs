@@ -0,0 +1,249 @@
+use anyhow::Context;
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+use site_content::{parse_front_matter, shortcode_arg, strip_date_prefix};
+use std::{fs, path::Path};
+
+/// Length of descriptions taken from the first paragraph of a page
+const DESCRIPTION_LENGTH: usize = 200;
+
+#[derive(Deserialize)]
+struct FrontMatter {
+    title: Option<String>,
+    description: Option<String>,
+    weight: Option<i64>,
+    date: Option<toml::value::Datetime>,
+    slug: Option<String>,
+    redirect_to: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    extra: Extra,
+}
+
+#[derive(Deserialize, Default)]
+struct Extra {
+    long_title: Option<String>,
+}
+
+/// A section or page of the content tree
+#[derive(Serialize)]
+pub struct Node {
+    pub title: String,
+    /// Path on the site, e.g. `/learn/book/introduction/`
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect_to: Option<String>,
+    #[serde(skip)]
+    weight: Option<i64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Node>,
+}
+
+/// Reads the content tree below `dir`, which is served at `path`.
+/// Folders with an `_index.md` are sections, folders with an `index.md` and other markdown files are pages.
+pub fn read_dir(dir: &Path, path: &str) -> anyhow::Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+
+    let mut entries: Vec<_> = fs::read_dir(dir)?.collect::<Result<_, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let entry_path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if entry_path.is_dir() {
+            let section = entry_path.join("_index.md");
+            let page = entry_path.join("index.md");
+            let index = [section, page].into_iter().find(|index| index.exists());
+
+            let children_path = format!("{path}{}/", strip_date_prefix(&name));
+            let children = read_dir(&entry_path, &children_path)?;
+            match index {
+                Some(index) => {
+                    if let Some(mut node) = read_node(&index, path, &name)? {
+                        node.children = children;
+                        nodes.push(node);
+                    }
+                }
+                // A folder without an index only groups its children
+                None => nodes.extend(children),
+            }
+        } else if name.ends_with(".md") && name != "_index.md" && name != "index.md" {
+            let name = name.trim_end_matches(".md");
+            nodes.extend(read_node(&entry_path, path, name)?);
+        }
+    }
+
+    sort(&mut nodes);
+    Ok(nodes)
+}
+
+fn read_node(file: &Path, parent_path: &str, name: &str) -> anyhow::Result<Option<Node>> {
+    let source = fs::read_to_string(file).with_context(|| format!("failed to read {:?}", file))?;
+    let (front_matter, body): (FrontMatter, _) = parse_front_matter(file, &source)?;
+    if front_matter.draft {
+        return Ok(None);
+    }
+
+    let slug = front_matter
+        .slug
+        .unwrap_or_else(|| strip_date_prefix(name).to_string());
+    Ok(Some(Node {
+        title: front_matter
+            .extra
+            .long_title
+            .or(front_matter.title)
+            .unwrap_or_else(|| slug.clone()),
+        path: format!("{parent_path}{slug}/"),
+        description: front_matter.description.or_else(|| first_paragraph(body)),
+        date: front_matter.date.map(|date| date.to_string()),
+        redirect_to: front_matter
+            .redirect_to
+            .map(|target| format!("/{}/", target.trim_matches('/'))),
+        weight: front_matter.weight,
+        children: Vec::new(),
+    }))
+}
+
+/// Weighted nodes come first, then dated ones from newest to oldest, then the rest by title
+fn sort(nodes: &mut [Node]) {
+    nodes.sort_by(|a, b| {
+        (a.weight.is_none(), a.weight)
+            .cmp(&(b.weight.is_none(), b.weight))
+            .then_with(|| b.date.cmp(&a.date))
+            .then_with(|| a.title.cmp(&b.title))
+    });
+}
+
+/// The text of the first markdown paragraph, with shortcodes replaced by their `name` argument
+fn first_paragraph(body: &str) -> Option<String> {
+    let mut paragraph: Option<String> = None;
+    let mut text = String::new();
+    for event in Parser::new(body) {
+        match (&mut paragraph, event) {
+            (None, Event::Start(Tag::Paragraph)) => paragraph = Some(String::new()),
+            (Some(text), Event::Text(t) | Event::Code(t)) => text.push_str(&t),
+            (Some(text), Event::SoftBreak | Event::HardBreak) => text.push(' '),
+            (Some(source), Event::End(TagEnd::Paragraph)) => {
+                // A paragraph holding only shortcodes, like a `picture`, has no text to describe
+                text = replace_shortcodes(source)
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                if !text.is_empty() {
+                    break;
+                }
+                paragraph = None;
+            }
+            _ => {}
+        }
+    }
+
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() <= DESCRIPTION_LENGTH {
+        return Some(text);
+    }
+    let truncated: String = text.chars().take(DESCRIPTION_LENGTH).collect();
+    let truncated = truncated
+        .rsplit_once(' ')
+        .map_or(truncated.as_str(), |(w, _)| w);
+    Some(format!("{truncated}…"))
+}
+
+/// `{{rust_type(..., name="App")}}` becomes `App`, other shortcodes are dropped
+fn replace_shortcodes(text: &str) -> String {
+    site_content::replace_shortcodes(text, |call| {
+        shortcode_arg(call, "name").unwrap_or_default().to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(name: &str, source: &str) -> anyhow::Result<Option<Node>> {
+        let dir = std::env::temp_dir().join("generate-site-summary-tests");
+        fs::create_dir_all(&dir)?;
+        let file = dir.join(format!("{name}.md"));
+        fs::write(&file, source)?;
+        read_node(&file, "/news/", name)
+    }
+
+    #[test]
+    fn replaces_shortcodes_with_their_name() {
+        assert_eq!(
+            replace_shortcodes(
+                "Add {{rust_type(type=\"struct\" crate=\"bevy_app\" name=\"App\")}} first"
+            ),
+            "Add App first"
+        );
+        assert_eq!(
+            replace_shortcodes("{{ picture(src=\"/a.png\") }}Text"),
+            "Text"
+        );
+    }
+
+    #[test]
+    fn reads_shortcodes_spanning_lines() {
+        let body = "Bevy has {{rust_type(type=\"trait\"\ncrate=\"bevy_app\"\nname=\"Plugin\")}} support.\n\nSecond";
+        assert_eq!(
+            first_paragraph(body).as_deref(),
+            Some("Bevy has Plugin support.")
+        );
+    }
+
+    #[test]
+    fn skips_empty_paragraphs() {
+        let body =
+            "<video src=\"a.mp4\"></video>\n\n{{ picture(src=\"/a.png\") }}\n\nFirst *real* `text`";
+        assert_eq!(first_paragraph(body).as_deref(), Some("First real text"));
+        assert_eq!(first_paragraph(""), None);
+        assert_eq!(first_paragraph("# Only a heading\n\n```\ncode\n```"), None);
+    }
+
+    #[test]
+    fn truncates_long_paragraphs_between_words() {
+        let description = first_paragraph(&"word ".repeat(100)).unwrap();
+        assert!(description.ends_with("word…"));
+        assert!(description.chars().count() <= DESCRIPTION_LENGTH + 1);
+    }
+
+    #[test]
+    fn reads_front_matter() {
+        let node = node(
+            "2020-08-10-introducing-bevy",
+            "+++\ntitle = \"Introducing Bevy\"\ndate = 2020-08-10\n[extra]\nlong_title = \"Introducing Bevy Engine\"\n+++\nBevy is here.",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(node.title, "Introducing Bevy Engine");
+        assert_eq!(node.path, "/news/introducing-bevy/");
+        assert_eq!(node.date.as_deref(), Some("2020-08-10"));
+        assert_eq!(node.description.as_deref(), Some("Bevy is here."));
+    }
+
+    #[test]
+    fn handles_front_matter_edge_cases() {
+        let custom = node(
+            "untitled",
+            "+++\nslug = \"custom\"\nredirect_to = \"learn/book\"\ndescription = \"Set\"\n+++\nIgnored",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(custom.title, "custom");
+        assert_eq!(custom.path, "/news/custom/");
+        assert_eq!(custom.redirect_to.as_deref(), Some("/learn/book/"));
+        assert_eq!(custom.description.as_deref(), Some("Set"));
+
+        let draft = node("draft", "+++\ntitle = \"Draft\"\ndraft = true\n+++\n").unwrap();
+        assert!(draft.is_none());
+        assert!(node("missing-delimiters", "title = \"No front matter\"").is_err());
+        assert!(node("invalid-toml", "+++\ntitle = \n+++\n").is_err());
+    }
+}
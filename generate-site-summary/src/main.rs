@@ -0,0 +1,164 @@
+use clap::Parser;
+use content::Node;
+use serde::{Deserialize, Serialize};
+use site_content::read_toml;
+use std::{fmt::Write, fs, path::PathBuf};
+
+mod content;
+
+/// Key pages that live outside of this site
+const EXTERNAL_LINKS: &[(&str, &str, &str)] = &[
+    (
+        "API Docs",
+        "https://docs.rs/bevy",
+        "Bevy's Rust API documentation, for every published version",
+    ),
+    (
+        "Source Code",
+        "https://github.com/bevyengine/bevy",
+        "The Bevy repository, including its examples",
+    ),
+    (
+        "crates.io",
+        "https://crates.io/crates/bevy",
+        "Published versions of the bevy crate",
+    ),
+];
+
+#[derive(Parser)]
+#[command(about = "Generate a machine-readable map of the site from the content tree")]
+struct Args {
+    #[arg(long, default_value = "config.toml")]
+    config: PathBuf,
+    #[arg(long, default_value = "content")]
+    content_dir: PathBuf,
+    /// Both files are served from the root of the site
+    #[arg(long, default_value = "static")]
+    output_dir: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct Config {
+    base_url: String,
+    title: String,
+    description: String,
+}
+
+#[derive(Serialize)]
+struct SiteIndex<'a> {
+    title: &'a str,
+    description: &'a str,
+    base_url: &'a str,
+    sections: &'a [Node],
+    external: Vec<External<'a>>,
+}
+
+#[derive(Serialize)]
+struct External<'a> {
+    title: &'a str,
+    url: &'a str,
+    description: &'a str,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let mut run = build_manifest::start!();
+    run.config(&args.config)?.input(&args.content_dir);
+
+    let config: Config = read_toml(&args.config)?;
+    let base_url = config.base_url.trim_end_matches('/');
+
+    let sections = content::read_dir(&args.content_dir, "/")?;
+
+    fs::create_dir_all(&args.output_dir)?;
+    fs::write(
+        args.output_dir.join("llms.txt"),
+        llms_txt(&config, base_url, &sections)?,
+    )?;
+    let index = SiteIndex {
+        title: &config.title,
+        description: &config.description,
+        base_url,
+        sections: &sections,
+        external: EXTERNAL_LINKS
+            .iter()
+            .map(|(title, url, description)| External {
+                title,
+                url,
+                description,
+            })
+            .collect(),
+    };
+    fs::write(
+        args.output_dir.join("site_index.json"),
+        serde_json::to_string_pretty(&index)? + "\n",
+    )?;
+
+    println!(
+        "wrote llms.txt and site_index.json for {} sections to {:?}",
+        sections.len(),
+        args.output_dir
+    );
+    run.output(args.output_dir.join("llms.txt"))
+        .output(args.output_dir.join("site_index.json"));
+    run.finish()
+}
+
+/// Follows the llms.txt format: a title, a quoted summary, then one list of links per section
+fn llms_txt(config: &Config, base_url: &str, sections: &[Node]) -> anyhow::Result<String> {
+    let mut output = String::new();
+    writeln!(output, "# {}\n", config.title)?;
+    writeln!(output, "> {}\n", config.description)?;
+
+    for section in sections {
+        writeln!(output, "## {}\n", section.title)?;
+        write_link(&mut output, base_url, section, 0)?;
+        for child in &section.children {
+            write_tree(&mut output, base_url, child, 1)?;
+        }
+        writeln!(output)?;
+    }
+
+    writeln!(output, "## External\n")?;
+    for (title, url, description) in EXTERNAL_LINKS {
+        writeln!(output, "- [{title}]({url}): {description}")?;
+    }
+    Ok(output)
+}
+
+fn write_tree(
+    output: &mut String,
+    base_url: &str,
+    node: &Node,
+    depth: usize,
+) -> anyhow::Result<()> {
+    write_link(output, base_url, node, depth)?;
+    for child in &node.children {
+        write_tree(output, base_url, child, depth + 1)?;
+    }
+    Ok(())
+}
+
+fn write_link(
+    output: &mut String,
+    base_url: &str,
+    node: &Node,
+    depth: usize,
+) -> anyhow::Result<()> {
+    // Redirecting sections are listed under the page they redirect to
+    let path = node.redirect_to.as_deref().unwrap_or(&node.path);
+    write!(
+        output,
+        "{}- [{}]({base_url}{path})",
+        "  ".repeat(depth),
+        node.title
+    )?;
+    if let Some(date) = &node.date {
+        write!(output, " ({date})")?;
+    }
+    if let Some(description) = &node.description {
+        write!(output, ": {description}")?;
+    }
+    writeln!(output)?;
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use anyhow::{bail, Context};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use site_content::read_toml;
+use std::{
+    fs,
+    io::{Cursor, Write},
+    path::{Path, PathBuf},
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, DateTime, ZipWriter};
+
+/// Matches the project created in the book's setup chapter
+const PROJECT_NAME: &str = "my_bevy_game";
+
+#[derive(Parser)]
+#[command(about = "Generate a downloadable starter project for each Bevy release")]
+struct Args {
+    #[arg(long, default_value = "generate-starters/starters.toml")]
+    starters: PathBuf,
+    #[arg(long, default_value = "generate-starters/templates")]
+    templates_dir: PathBuf,
+    /// Archives and `manifest.json` are written here, and served from `/starters/`
+    #[arg(long, default_value = "static/starters")]
+    output_dir: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct Starters {
+    release: Vec<Release>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Release {
+    bevy: String,
+    main: String,
+    /// Dated nightly written to `rust-toolchain`, the fast builds config needs nightly flags
+    toolchain: String,
+    lockfile: String,
+}
+
+#[derive(Serialize)]
+struct Manifest {
+    latest: String,
+    starters: Vec<Starter>,
+}
+
+#[derive(Serialize)]
+struct Starter {
+    bevy_version: String,
+    archive: String,
+    sha256: String,
+    bytes: usize,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let mut run = build_manifest::start!();
+    run.config(&args.starters)?.input(&args.templates_dir);
+
+    let starters: Starters = read_toml(&args.starters)?;
+    let Some(latest) = starters.release.last() else {
+        bail!("{:?} doesn't list any release", args.starters);
+    };
+    let fast_builds = read_template(&args.templates_dir.join("config_fast_builds.toml"))?;
+
+    fs::create_dir_all(&args.output_dir)?;
+
+    let mut manifest = Manifest {
+        latest: latest.bevy.clone(),
+        starters: Vec::new(),
+    };
+    for release in &starters.release {
+        let main = read_template(&args.templates_dir.join(&release.main))
+            .with_context(|| format!("missing template for bevy {}", release.bevy))?;
+        let lockfile = read_template(&args.templates_dir.join(&release.lockfile))
+            .with_context(|| format!("missing lockfile for bevy {}", release.bevy))?;
+        if !lockfile.contains(&format!(
+            "name = \"bevy\"\nversion = \"{}\"\n",
+            release.bevy
+        )) {
+            bail!("{:?} doesn't lock bevy {}", release.lockfile, release.bevy);
+        }
+        if !release.toolchain.starts_with("nightly-") {
+            bail!(
+                "bevy {} must use a dated nightly toolchain, like `nightly-2020-08-10`",
+                release.bevy
+            );
+        }
+        let files = [
+            ("Cargo.toml", cargo_toml(&release.bevy)),
+            ("Cargo.lock", lockfile),
+            ("src/main.rs", main),
+            (".cargo/config", fast_builds.clone()),
+            ("rust-toolchain", format!("{}\n", release.toolchain)),
+            (".gitignore", "/target\n".to_string()),
+        ];
+        let archive = zip_project(&files)?;
+
+        let name = format!("bevy-{}-starter.zip", release.bevy);
+        fs::write(args.output_dir.join(&name), &archive)?;
+        manifest.starters.push(Starter {
+            bevy_version: release.bevy.clone(),
+            archive: format!("/starters/{name}"),
+            sha256: format!("{:x}", Sha256::digest(&archive)),
+            bytes: archive.len(),
+        });
+        println!("wrote {name}");
+    }
+
+    fs::write(
+        args.output_dir.join("manifest.json"),
+        serde_json::to_string_pretty(&manifest)? + "\n",
+    )?;
+    run.output(&args.output_dir);
+    run.finish()
+}
+
+/// Archives always use LF, even when the templates were checked out with CRLF
+fn read_template(path: &Path) -> anyhow::Result<String> {
+    let template =
+        fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?;
+    Ok(template.replace("\r\n", "\n"))
+}
+
+fn cargo_toml(bevy_version: &str) -> String {
+    format!(
+        r#"[package]
+name = "{PROJECT_NAME}"
+version = "0.1.0"
+edition = "2018"
+
+[dependencies]
+bevy = "={bevy_version}"
+"#
+    )
+}
+
+/// Zips the files under a `my_bevy_game/` folder. Timestamps are fixed so regenerating an
+/// unchanged release produces the exact same archive.
+fn zip_project(files: &[(&str, String)]) -> anyhow::Result<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(DateTime::default())
+        .unix_permissions(0o644);
+    for (path, content) in files {
+        zip.start_file(format!("{PROJECT_NAME}/{path}"), options)?;
+        zip.write_all(content.as_bytes())?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
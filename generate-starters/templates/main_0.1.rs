@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+fn main() {
+    App::build().add_default_plugins().run();
+}
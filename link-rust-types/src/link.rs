@@ -0,0 +1,103 @@
+use crate::symbols::Symbols;
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use std::ops::Range;
+
+/// A backticked name that will be replaced by a `rust_type` shortcode
+pub struct Link {
+    pub name: String,
+    pub range: Range<usize>,
+    pub shortcode: String,
+}
+
+/// Finds the inline code spans in `markdown` that are exactly the name of a known symbol.
+/// Code inside links, headings and images is left alone, as are code blocks.
+/// Links point to the docs of the release the symbols were built from, which a post is about.
+pub fn find_links(markdown: &str, symbols: &Symbols) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut skip_depth = 0;
+    for (event, range) in Parser::new(markdown).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Link { .. } | Tag::Image { .. } | Tag::Heading { .. }) => {
+                skip_depth += 1
+            }
+            Event::End(TagEnd::Link | TagEnd::Image | TagEnd::Heading(_)) => skip_depth -= 1,
+            Event::Code(code) if skip_depth == 0 => {
+                let Some(symbol) = symbols.symbols.get(code.as_ref()) else {
+                    continue;
+                };
+                let module = if symbol.module.is_empty() {
+                    String::new()
+                } else {
+                    format!(" mod=\"{}\"", symbol.module)
+                };
+                links.push(Link {
+                    name: code.to_string(),
+                    range,
+                    shortcode: format!(
+                        "{{{{rust_type(type=\"{}\" crate=\"{}\" version=\"{}\"{module} name=\"{code}\" no_mod=true)}}}}",
+                        symbol.kind, symbol.krate, symbols.release
+                    ),
+                });
+            }
+            _ => {}
+        }
+    }
+    links
+}
+
+pub fn apply(markdown: &str, links: &[Link]) -> String {
+    let mut output = String::with_capacity(markdown.len());
+    let mut end = 0;
+    for link in links {
+        output.push_str(&markdown[end..link.range.start]);
+        output.push_str(&link.shortcode);
+        end = link.range.end;
+    }
+    output.push_str(&markdown[end..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbols::{Export, Symbol};
+
+    fn symbols() -> Symbols {
+        let export = |name: &str, krate: &str, module: &str| Export {
+            name: name.to_string(),
+            item: "0:1".to_string(),
+            symbol: Symbol {
+                krate: krate.to_string(),
+                module: module.to_string(),
+                kind: "struct".to_string(),
+            },
+        };
+        Symbols::from_crates(
+            "0.1.3".to_string(),
+            vec![
+                vec![export("Commands", "bevy_ecs", "")],
+                vec![export("ActiveCameras", "bevy_render", "camera")],
+            ],
+        )
+    }
+
+    #[test]
+    fn links_known_names() {
+        let markdown = "Use `Commands` and `ActiveCameras`, not `Unknown`.";
+        let links = find_links(markdown, &symbols());
+        let names: Vec<_> = links.iter().map(|link| link.name.as_str()).collect();
+        assert_eq!(names, ["Commands", "ActiveCameras"]);
+        assert_eq!(
+            apply(markdown, &links),
+            "Use {{rust_type(type=\"struct\" crate=\"bevy_ecs\" version=\"0.1.3\" name=\"Commands\" no_mod=true)}} \
+             and {{rust_type(type=\"struct\" crate=\"bevy_render\" version=\"0.1.3\" mod=\"camera\" name=\"ActiveCameras\" no_mod=true)}}, \
+             not `Unknown`."
+        );
+    }
+
+    #[test]
+    fn skips_links_headings_and_code_blocks() {
+        let markdown = "## `Commands`\n\n[`Commands`](https://docs.rs)\n\n```rust\nCommands\n```\n\n`Commands::spawn`\n";
+        assert!(find_links(markdown, &symbols()).is_empty());
+    }
+}
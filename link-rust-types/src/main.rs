@@ -0,0 +1,156 @@
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use serde::Deserialize;
+use site_content::parse_front_matter;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+use symbols::Symbols;
+use walkdir::WalkDir;
+
+mod link;
+mod symbols;
+
+#[derive(Parser)]
+#[command(about = "Link backticked Bevy type names in release posts to their docs.rs page")]
+struct Args {
+    #[arg(long, default_value = "link-rust-types/symbols.toml")]
+    symbols: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Build the symbol map from the rustdoc JSON of each bevy_* crate at a release
+    Symbols {
+        #[arg(long)]
+        release: String,
+        /// Output of `cargo +nightly rustdoc -p <crate> -- -Z unstable-options --output-format json`
+        #[arg(required = true)]
+        rustdoc_json: Vec<PathBuf>,
+    },
+    /// Replace backticked symbol names with `rust_type` shortcodes
+    Link {
+        #[arg(long, default_value = "content")]
+        content_dir: PathBuf,
+        /// List the names that would be linked and fail if there are any, without changing files
+        #[arg(long)]
+        check: bool,
+    },
+}
+
+#[derive(Deserialize)]
+struct FrontMatter {
+    #[serde(default)]
+    extra: Extra,
+}
+
+#[derive(Deserialize, Default)]
+struct Extra {
+    category: Option<String>,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let args = Args::parse();
+    let mut run = build_manifest::start!();
+    let code = match args.command {
+        Command::Symbols {
+            release,
+            rustdoc_json,
+        } => {
+            for path in &rustdoc_json {
+                run.input(path);
+            }
+            let crates = rustdoc_json
+                .iter()
+                .map(|path| symbols::read_rustdoc_json(path))
+                .collect::<anyhow::Result<_>>()?;
+            let symbols = Symbols::from_crates(release, crates);
+            fs::write(
+                &args.symbols,
+                format!(
+                    "# Generated by `cargo run -p link-rust-types -- symbols`, do not edit by hand\n\n{}",
+                    toml::to_string(&symbols)?
+                ),
+            )?;
+            println!(
+                "wrote {} symbols to {:?}, {} ambiguous names left out",
+                symbols.symbols.len(),
+                args.symbols,
+                symbols.ambiguous.len()
+            );
+            run.output(&args.symbols);
+            ExitCode::SUCCESS
+        }
+        Command::Link { content_dir, check } => {
+            run.config(&args.symbols)?.input(&content_dir);
+            let symbols = Symbols::from_file(&args.symbols)?;
+            link(&content_dir, &symbols, check, &mut run)?
+        }
+    };
+    run.finish()?;
+    Ok(code)
+}
+
+fn link(
+    content_dir: &Path,
+    symbols: &Symbols,
+    check: bool,
+    run: &mut build_manifest::Run,
+) -> anyhow::Result<ExitCode> {
+    let mut total = 0;
+    for entry in WalkDir::new(content_dir).sort_by_file_name() {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "md") {
+            continue;
+        }
+        let source =
+            fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?;
+        let (front_matter, body): (FrontMatter, _) = parse_front_matter(path, &source)?;
+        if !is_linked(path, &front_matter) {
+            continue;
+        }
+        // Every post that gets linked is an output, even when it's already up to date, so
+        // reruns record the same files
+        if !check {
+            run.output(path);
+        }
+
+        let links = link::find_links(body, symbols);
+        if links.is_empty() {
+            continue;
+        }
+        total += links.len();
+        if check {
+            for link in &links {
+                println!("{}: `{}` can be linked", path.display(), link.name);
+            }
+        } else {
+            let offset = source.len() - body.len();
+            fs::write(
+                path,
+                format!("{}{}", &source[..offset], link::apply(body, &links)),
+            )?;
+            println!("linked {} names in {}", links.len(), path.display());
+        }
+    }
+
+    if check && total > 0 {
+        println!(
+            "found {total} unlinked names, run `cargo run -p link-rust-types -- link` to link them"
+        );
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Release posts and migration guides, where readers most often look up the types being discussed
+fn is_linked(path: &Path, front_matter: &FrontMatter) -> bool {
+    path.components()
+        .any(|component| component.as_os_str() == "migration-guides")
+        || front_matter.extra.category.as_deref() == Some("release")
+}
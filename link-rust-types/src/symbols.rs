@@ -0,0 +1,288 @@
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::Path,
+};
+
+/// Where a type is documented on docs.rs, as the arguments of the `rust_type` shortcode
+#[derive(Serialize, Deserialize)]
+pub struct Symbol {
+    #[serde(rename = "crate")]
+    pub krate: String,
+    /// Module path below the crate, e.g. `world` or `render::camera`
+    #[serde(rename = "mod", default, skip_serializing_if = "String::is_empty")]
+    pub module: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// A public path of an item, several exports can name the same item through re-exports
+pub struct Export {
+    pub name: String,
+    /// Rustdoc id of the item, the same for every path it is re-exported at
+    pub item: String,
+    pub symbol: Symbol,
+}
+
+/// The symbol map written to `symbols.toml`
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Symbols {
+    /// Bevy release the rustdoc JSON was built from
+    pub release: String,
+    pub symbols: BTreeMap<String, Symbol>,
+    /// Names of several different items, in one crate or more, which are never linked automatically
+    #[serde(default)]
+    pub ambiguous: Vec<String>,
+}
+
+impl Symbols {
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        site_content::read_toml(path)
+    }
+
+    /// Merges the public items of several crates. A name shared by different items is ambiguous.
+    pub fn from_crates(release: String, crates: Vec<Vec<Export>>) -> Self {
+        let mut candidates: BTreeMap<String, Vec<(String, Symbol)>> = BTreeMap::new();
+        for exports in crates {
+            for export in exports {
+                candidates
+                    .entry(export.name)
+                    .or_default()
+                    .push((export.item, export.symbol));
+            }
+        }
+
+        let mut symbols = BTreeMap::new();
+        let mut ambiguous = Vec::new();
+        for (name, mut found) in candidates {
+            // Re-exports make the same item public at several paths, the shortest one is canonical
+            found.sort_by_key(|(_, symbol)| {
+                (symbol.module.matches("::").count(), symbol.module.len())
+            });
+            let (item, symbol) = &found[0];
+            if found
+                .iter()
+                .all(|(other_item, other)| other_item == item && other.krate == symbol.krate)
+            {
+                symbols.insert(name, found.swap_remove(0).1);
+            } else {
+                ambiguous.push(name);
+            }
+        }
+        Symbols {
+            release,
+            symbols,
+            ambiguous,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Crate {
+    root: Value,
+    index: HashMap<String, Item>,
+}
+
+#[derive(Deserialize)]
+struct Item {
+    name: Option<String>,
+    visibility: Value,
+    inner: HashMap<String, Value>,
+}
+
+/// Reads the public items of a crate from `cargo rustdoc -- -Z unstable-options --output-format json`.
+/// Items are listed at the public paths they are reachable from, since that's where docs.rs puts their page.
+pub fn read_rustdoc_json(path: &Path) -> anyhow::Result<Vec<Export>> {
+    let source = fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?;
+    let krate: Crate =
+        serde_json::from_str(&source).with_context(|| format!("failed to parse {:?}", path))?;
+
+    let root = krate
+        .index
+        .get(&id(&krate.root))
+        .context("the root module is missing from the index")?;
+    let Some(name) = root.name.clone() else {
+        bail!("the root module has no name");
+    };
+
+    let mut symbols = Vec::new();
+    let mut walker = Walker {
+        krate: &krate,
+        name,
+        symbols: &mut symbols,
+    };
+    walker.visit_module(root, &[])?;
+    Ok(symbols)
+}
+
+struct Walker<'a> {
+    krate: &'a Crate,
+    name: String,
+    symbols: &'a mut Vec<Export>,
+}
+
+impl Walker<'_> {
+    fn visit_module(&mut self, module: &Item, path: &[String]) -> anyhow::Result<()> {
+        let items = module
+            .inner
+            .get("module")
+            .and_then(|module| module.get("items"))
+            .and_then(Value::as_array)
+            .context("expected a module")?;
+        for item_id in items {
+            let Some(item) = self.krate.index.get(&id(item_id)) else {
+                continue;
+            };
+            if item.visibility != "public" {
+                continue;
+            }
+            match item.inner.get("use") {
+                Some(import) => self.visit_use(import, path)?,
+                None => self.visit_item(&id(item_id), item, item.name.as_deref(), path)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_use(&mut self, import: &Value, path: &[String]) -> anyhow::Result<()> {
+        // Items re-exported from other crates are documented there
+        let Some(target_id) = import.get("id").filter(|id| !id.is_null()).map(id) else {
+            return Ok(());
+        };
+        let Some(target) = self.krate.index.get(&target_id) else {
+            return Ok(());
+        };
+        if import.get("is_glob").and_then(Value::as_bool) == Some(true) {
+            if target.inner.contains_key("module") {
+                self.visit_module(target, path)?;
+            }
+            return Ok(());
+        }
+        self.visit_item(
+            &target_id,
+            target,
+            import.get("name").and_then(Value::as_str),
+            path,
+        )
+    }
+
+    fn visit_item(
+        &mut self,
+        item_id: &str,
+        item: &Item,
+        name: Option<&str>,
+        path: &[String],
+    ) -> anyhow::Result<()> {
+        let Some(name) = name else {
+            return Ok(());
+        };
+        // The prelude only repeats items that are documented elsewhere
+        if item.inner.contains_key("module") {
+            if name != "prelude" {
+                self.visit_module(item, &[path, &[name.to_string()]].concat())?;
+            }
+            return Ok(());
+        }
+        let Some(kind) = item.inner.keys().next().and_then(|kind| docs_kind(kind)) else {
+            return Ok(());
+        };
+        self.symbols.push(Export {
+            name: name.to_string(),
+            item: item_id.to_string(),
+            symbol: Symbol {
+                krate: self.name.clone(),
+                module: path.join("::"),
+                kind: kind.to_string(),
+            },
+        });
+        Ok(())
+    }
+}
+
+/// Only types are linked, backticked function and macro names are too often local examples.
+/// Kinds are named like the docs.rs page prefixes the `rust_type` shortcode expects.
+fn docs_kind(kind: &str) -> Option<&'static str> {
+    Some(match kind {
+        "struct" => "struct",
+        "enum" => "enum",
+        "union" => "union",
+        "trait" => "trait",
+        "type_alias" | "typedef" => "type",
+        _ => return None,
+    })
+}
+
+/// Ids are strings in older rustdoc JSON formats and numbers in newer ones
+fn id(value: &Value) -> String {
+    match value {
+        Value::String(id) => id.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export(name: &str, item: &str, krate: &str, module: &str) -> Export {
+        Export {
+            name: name.to_string(),
+            item: item.to_string(),
+            symbol: Symbol {
+                krate: krate.to_string(),
+                module: module.to_string(),
+                kind: "struct".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn picks_the_shortest_path_of_an_item() {
+        let symbols = Symbols::from_crates(
+            "0.1.3".to_string(),
+            vec![vec![
+                export("Camera", "0:1", "bevy_render", "camera::camera"),
+                export("Camera", "0:1", "bevy_render", "camera"),
+            ]],
+        );
+        assert_eq!(symbols.symbols["Camera"].module, "camera");
+        assert!(symbols.ambiguous.is_empty());
+    }
+
+    #[test]
+    fn leaves_out_names_of_different_items_in_a_crate() {
+        let symbols = Symbols::from_crates(
+            "0.1.3".to_string(),
+            vec![vec![
+                export("Node", "0:1", "bevy_render", "render_graph"),
+                export("Node", "0:2", "bevy_render", "pass::node"),
+                export("Camera", "0:3", "bevy_render", "camera"),
+            ]],
+        );
+        assert!(!symbols.symbols.contains_key("Node"));
+        assert!(symbols.symbols.contains_key("Camera"));
+        assert_eq!(symbols.ambiguous, ["Node"]);
+    }
+
+    #[test]
+    fn leaves_out_names_from_several_crates() {
+        let symbols = Symbols::from_crates(
+            "0.1.3".to_string(),
+            vec![
+                vec![
+                    export("Rect", "0:1", "bevy_math", ""),
+                    export("Vec2", "0:2", "bevy_math", ""),
+                ],
+                // Ids are only unique within a crate
+                vec![export("Rect", "0:1", "bevy_sprite", "")],
+            ],
+        );
+        assert!(!symbols.symbols.contains_key("Rect"));
+        assert!(symbols.symbols.contains_key("Vec2"));
+        assert_eq!(symbols.ambiguous, ["Rect"]);
+    }
+}
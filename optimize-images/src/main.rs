@@ -0,0 +1,184 @@
+use anyhow::Context;
+use clap::Parser;
+use image::{imageops::FilterType, DynamicImage, GenericImageView, ImageReader};
+use serde::Serialize;
+use site_content::strip_date_prefix;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use walkdir::WalkDir;
+
+const EXTENSIONS: &[&str] = &["png", "jpg", "jpeg"];
+
+#[derive(Parser)]
+#[command(about = "Generate resized WebP variants of the site's images")]
+struct Args {
+    /// Folders scanned for images. Paths under `static/` and `content/` are mapped to their URL on the site
+    #[arg(long, default_values = ["static", "content"])]
+    dirs: Vec<PathBuf>,
+    /// Widths of the generated variants, images are never upscaled
+    #[arg(long, value_delimiter = ',', default_value = "640,1280,1920")]
+    widths: Vec<u32>,
+    /// WebP quality, from 0 to 100
+    #[arg(long, default_value_t = 80.0)]
+    quality: f32,
+    /// Regenerate variants even when they are newer than their original
+    #[arg(long)]
+    force: bool,
+    /// Manifest mapping each original to its variants
+    #[arg(long, default_value = "static/image_manifest.json")]
+    manifest: PathBuf,
+}
+
+#[derive(Serialize)]
+struct Variant {
+    /// File name of the variant, which sits in the same folder as the original
+    path: String,
+    width: u32,
+    height: u32,
+    bytes: u64,
+}
+
+#[derive(Serialize)]
+struct ManifestEntry {
+    width: u32,
+    height: u32,
+    bytes: u64,
+    variants: Vec<Variant>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let mut run = build_manifest::start!();
+
+    let mut manifest = BTreeMap::new();
+    let (mut original_bytes, mut optimized_bytes) = (0, 0);
+
+    for dir in &args.dirs {
+        run.input(dir);
+        for entry in WalkDir::new(dir).sort_by_file_name() {
+            let entry = entry?;
+            let path = entry.path();
+            if !is_original(path) {
+                continue;
+            }
+
+            let entry =
+                optimize(&args, path).with_context(|| format!("failed to optimize {:?}", path))?;
+            original_bytes += entry.bytes;
+            optimized_bytes += entry.variants.last().map_or(entry.bytes, |v| v.bytes);
+            for variant in &entry.variants {
+                run.output(path.with_file_name(&variant.path));
+            }
+            manifest.insert(site_path(dir, path), entry);
+        }
+    }
+
+    fs::write(
+        &args.manifest,
+        serde_json::to_string_pretty(&manifest)? + "\n",
+    )?;
+    run.output(&args.manifest);
+    println!(
+        "optimized {} images: {} KiB of originals, {} KiB for the largest variants",
+        manifest.len(),
+        original_bytes / 1024,
+        optimized_bytes / 1024
+    );
+    run.finish()
+}
+
+fn is_original(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+fn optimize(args: &Args, path: &Path) -> anyhow::Result<ManifestEntry> {
+    let bytes = fs::metadata(path)?.len();
+    let modified = fs::metadata(path)?.modified()?;
+    // Some originals don't match their extension (`mountains.png` is a JPEG), so sniff the format
+    let image = ImageReader::open(path)?.with_guessed_format()?.decode()?;
+    let (width, height) = image.dimensions();
+
+    let mut widths: Vec<u32> = args.widths.iter().copied().filter(|w| *w < width).collect();
+    // Always keep a full size variant so every original gets a WebP
+    widths.push(width);
+    widths.sort_unstable();
+    widths.dedup();
+
+    let mut variants = Vec::new();
+    for variant_width in widths {
+        let variant_path = variant_path(path, variant_width);
+        let up_to_date = !args.force
+            && fs::metadata(&variant_path)
+                .and_then(|m| m.modified())
+                .is_ok_and(|variant_modified| variant_modified >= modified);
+
+        let variant_height = height * variant_width / width;
+        if !up_to_date {
+            let resized = if variant_width == width {
+                image.clone()
+            } else {
+                image.resize(variant_width, variant_height, FilterType::Lanczos3)
+            };
+            fs::write(&variant_path, encode_webp(&resized, args.quality)?)?;
+        }
+
+        variants.push(Variant {
+            path: variant_path
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string(),
+            width: variant_width,
+            height: variant_height,
+            bytes: fs::metadata(&variant_path)?.len(),
+        });
+    }
+
+    Ok(ManifestEntry {
+        width,
+        height,
+        bytes,
+        variants,
+    })
+}
+
+fn encode_webp(image: &DynamicImage, quality: f32) -> anyhow::Result<Vec<u8>> {
+    // The encoder only accepts 8 bit RGB(A)
+    let image = if image.color().has_alpha() {
+        DynamicImage::ImageRgba8(image.to_rgba8())
+    } else {
+        DynamicImage::ImageRgb8(image.to_rgb8())
+    };
+    let encoder = webp::Encoder::from_image(&image).map_err(|e| anyhow::anyhow!("{e}"))?;
+    Ok(encoder.encode(quality).to_vec())
+}
+
+/// `boat.png` becomes `boat.1280w.webp`, next to the original so colocated content assets keep working
+fn variant_path(path: &Path, width: u32) -> PathBuf {
+    let stem = path.file_stem().unwrap().to_string_lossy();
+    path.with_file_name(format!("{stem}.{width}w.webp"))
+}
+
+/// The URL path of an image, `static/assets/boat.png` is `/assets/boat.png`
+/// and `content/news/2020-08-10-introducing-bevy/boat.png` is `/news/introducing-bevy/boat.png`
+fn site_path(dir: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(dir).unwrap();
+    let is_content = dir.file_name().is_some_and(|name| name == "content");
+    let components: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .map(|c| {
+            if is_content {
+                strip_date_prefix(&c).to_string()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("/{}", components.join("/"))
+}
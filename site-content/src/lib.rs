@@ -0,0 +1,248 @@
+//! Helpers shared by the tools that read the Zola content folder and the site's TOML files.
+
+use anyhow::{bail, Context};
+use serde::de::DeserializeOwned;
+use std::{env, fs, path::Path};
+
+pub const DELIMITER: &str = "+++";
+
+/// Splits a page into its TOML front matter and its markdown body
+pub fn split_front_matter(source: &str) -> anyhow::Result<(&str, &str)> {
+    let Some(rest) = source.trim_start().strip_prefix(DELIMITER) else {
+        bail!("missing opening `{DELIMITER}`");
+    };
+    match rest.split_once(&format!("\n{DELIMITER}")) {
+        Some(split) => Ok(split),
+        None => bail!("missing closing `{DELIMITER}`"),
+    }
+}
+
+/// Splits a page and parses its front matter, see [`parse_toml`] for the errors
+pub fn parse_front_matter<'a, T: DeserializeOwned>(
+    path: &Path,
+    source: &'a str,
+) -> anyhow::Result<(T, &'a str)> {
+    let (front_matter, body) =
+        split_front_matter(source).with_context(|| format!("failed to parse {:?}", path))?;
+    let offset = source.len() - front_matter.len() - DELIMITER.len() - body.len() - 1;
+    let first_line = source[..offset].matches('\n').count();
+    Ok((parse_toml_at(path, front_matter, first_line)?, body))
+}
+
+/// Reads a TOML file, see [`parse_toml`] for the errors
+pub fn read_toml<T: DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    let source = fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?;
+    parse_toml(path, &source)
+}
+
+/// Parses a TOML file, naming the file, line and column of any error. serde's message names the
+/// unknown or missing field and lists the valid ones. On GitHub Actions the error is also printed
+/// as an annotation, so it shows up on the line of the pull request that caused it.
+pub fn parse_toml<T: DeserializeOwned>(path: &Path, source: &str) -> anyhow::Result<T> {
+    parse_toml_at(path, source, 0)
+}
+
+/// Parses TOML that starts after `first_line` lines of `path`
+fn parse_toml_at<T: DeserializeOwned>(
+    path: &Path,
+    source: &str,
+    first_line: usize,
+) -> anyhow::Result<T> {
+    toml::from_str(source).map_err(|error| {
+        let message = error.message().trim_end();
+        let Some(span) = error.span() else {
+            annotate(path, None, message);
+            return anyhow::anyhow!("failed to parse {:?}: {message}", path);
+        };
+        let before = &source[..span.start];
+        let line = first_line + before.matches('\n').count() + 1;
+        let column = before
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count()
+            + 1;
+        annotate(path, Some((line, column)), message);
+        anyhow::anyhow!(
+            "failed to parse {:?}: line {line}, column {column}: {message}",
+            path
+        )
+    })
+}
+
+fn annotate(path: &Path, position: Option<(usize, usize)>, message: &str) {
+    if env::var_os("GITHUB_ACTIONS").is_none_or(|value| value != "true") {
+        return;
+    }
+    let position = position
+        .map(|(line, column)| format!(",line={line},col={column}"))
+        .unwrap_or_default();
+    let message = message
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A");
+    println!("::error file={}{position}::{message}", path.display());
+}
+
+/// Zola removes a leading `YYYY-MM-DD-` or `YYYY-MM-DD_` from file names when building the slug
+pub fn strip_date_prefix(name: &str) -> &str {
+    let bytes = name.as_bytes();
+    let is_date = bytes.len() > 11
+        && bytes[..10].iter().enumerate().all(|(i, b)| {
+            if i == 4 || i == 7 {
+                *b == b'-'
+            } else {
+                b.is_ascii_digit()
+            }
+        })
+        && matches!(bytes[10], b'-' | b'_');
+    if is_date {
+        &name[11..]
+    } else {
+        name
+    }
+}
+
+/// Replaces every `{{ shortcode(...) }}` call with what `render` returns for it, since only
+/// Zola can render the templates. `render` is given the call without the braces.
+pub fn replace_shortcodes(text: &str, mut render: impl FnMut(&str) -> String) -> String {
+    let mut output = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        output.push_str(&render(rest[start + 2..start + end].trim()));
+        rest = &rest[start + end + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// The name of a shortcode call, e.g. `rust_type` for `rust_type(name="App")`
+pub fn shortcode_name(call: &str) -> &str {
+    call.split('(').next().unwrap_or_default().trim()
+}
+
+/// The value of a string argument of a shortcode call, e.g. `App` for `name` in `rust_type(name="App")`
+pub fn shortcode_arg<'a>(call: &'a str, arg: &str) -> Option<&'a str> {
+    let pattern = format!("{arg}=\"");
+    let mut offset = 0;
+    while let Some(index) = call[offset..].find(&pattern) {
+        let start = offset + index;
+        let is_whole_name = call[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric() && c != '_');
+        let value = &call[start + pattern.len()..];
+        if is_whole_name {
+            return value.split_once('"').map(|(value, _)| value);
+        }
+        offset = start + pattern.len();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_date_prefixes() {
+        assert_eq!(
+            strip_date_prefix("2020-08-10-introducing-bevy"),
+            "introducing-bevy"
+        );
+        assert_eq!(
+            strip_date_prefix("2020-08-10_introducing-bevy"),
+            "introducing-bevy"
+        );
+        assert_eq!(strip_date_prefix("2020-08-10"), "2020-08-10");
+        assert_eq!(strip_date_prefix("learn"), "learn");
+    }
+
+    #[test]
+    fn keeps_non_ascii_names() {
+        assert_eq!(strip_date_prefix("2020-08-1é-post"), "2020-08-1é-post");
+        assert_eq!(strip_date_prefix("ééééééé"), "ééééééé");
+    }
+
+    #[test]
+    fn splits_front_matter() {
+        let (front_matter, body) =
+            split_front_matter("\n+++\ntitle = \"Bevy\"\n+++\nHello").unwrap();
+        assert_eq!(front_matter, "\ntitle = \"Bevy\"");
+        assert_eq!(body, "\nHello");
+        assert!(split_front_matter("title = \"Bevy\"").is_err());
+        assert!(split_front_matter("+++\ntitle = \"Bevy\"").is_err());
+    }
+
+    #[test]
+    fn replaces_shortcodes() {
+        let text = "Use {{ rust_type(type=\"struct\", name=\"App\") }} and {{foo()}}.";
+        let replaced = replace_shortcodes(text, |call| {
+            shortcode_arg(call, "name").unwrap_or_default().to_string()
+        });
+        assert_eq!(replaced, "Use App and .");
+    }
+
+    #[test]
+    fn keeps_unclosed_shortcodes() {
+        assert_eq!(replace_shortcodes("a {{ b", |_| String::new()), "a {{ b");
+    }
+
+    #[test]
+    fn reads_shortcode_calls() {
+        let call = "rust_type(type=\"struct\" crate=\"bevy_ui\"\nname=\"Node\" no_mod=true)";
+        assert_eq!(shortcode_name(call), "rust_type");
+        assert_eq!(shortcode_arg(call, "type"), Some("struct"));
+        assert_eq!(shortcode_arg(call, "name"), Some("Node"));
+        assert_eq!(shortcode_arg(call, "mod"), None);
+        assert_eq!(shortcode_arg("rust_type(no_mod=\"a\")", "mod"), None);
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Posting {
+        title: String,
+        kind: String,
+    }
+
+    #[test]
+    fn names_the_line_of_unknown_fields() {
+        let source = "title = \"Bevy\"\nkind = \"job\"\nsalary = 1\n";
+        let error = parse_toml::<Posting>(Path::new("jobs/bevy.toml"), source).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to parse \"jobs/bevy.toml\": line 3, column 1: unknown field `salary`, expected `title` or `kind`"
+        );
+    }
+
+    #[test]
+    fn names_missing_fields() {
+        let error = parse_toml::<Posting>(Path::new("jobs/bevy.toml"), "title = \"Bevy\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("missing field `kind`"), "{error}");
+    }
+
+    #[test]
+    fn counts_front_matter_lines_from_the_file() {
+        let source = "\n+++\ntitle = \"Bevy\"\nkind = 1\n+++\nBody";
+        let error = parse_front_matter::<Posting>(Path::new("index.md"), source)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("line 4, column 8"), "{error}");
+
+        let source = "+++\ntitle = \"Bevy\"\nkind = \"job\"\n+++\nBody";
+        let (posting, body) = parse_front_matter::<Posting>(Path::new("index.md"), source).unwrap();
+        assert_eq!(
+            (posting.title.as_str(), posting.kind.as_str()),
+            ("Bevy", "job")
+        );
+        assert_eq!(body, "\nBody");
+    }
+}
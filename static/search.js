@@ -0,0 +1,60 @@
+// searches the index written by generate-search-index. the query and section come from the url so
+// that the opensearch descriptors written by generate-opensearch can link straight to results.
+
+function loadSearchIndex() {
+    return fetch("/search_index.json.gz").then(function (response) {
+        var stream = response.body.pipeThrough(new DecompressionStream("gzip"));
+        return new Response(stream).json();
+    });
+}
+
+function matchesQuery(document, words) {
+    var text = (document.title + " " + document.excerpt + " " + document.headings.map(function (heading) {
+        return heading.text;
+    }).join(" ")).toLowerCase();
+    return words.every(function (word) {
+        return text.indexOf(word) != -1;
+    });
+}
+
+function showResults(results, documents) {
+    results.innerHTML = "";
+    if (documents.length == 0) {
+        var empty = document.createElement("li");
+        empty.textContent = "No results";
+        results.appendChild(empty);
+        return;
+    }
+    documents.forEach(function (doc) {
+        var item = document.createElement("li");
+        var link = document.createElement("a");
+        link.href = doc.url;
+        link.textContent = doc.title;
+        var excerpt = document.createElement("p");
+        excerpt.textContent = doc.excerpt;
+        item.appendChild(link);
+        item.appendChild(excerpt);
+        results.appendChild(item);
+    });
+}
+
+window.onload = function () {
+    var results = document.getElementById("search-results");
+    var params = new URLSearchParams(window.location.search);
+    var query = params.get(results.dataset.queryParameter) || "";
+    var section = params.get(results.dataset.sectionParameter) || "";
+    document.getElementById("search-query").value = query;
+    document.getElementById("search-section").value = section;
+
+    var words = query.toLowerCase().split(/\s+/).filter(function (word) {
+        return word.length > 0;
+    });
+    if (words.length == 0) {
+        return;
+    }
+    loadSearchIndex().then(function (index) {
+        showResults(results, index.documents.filter(function (doc) {
+            return (section == "" || doc.section == section) && matchesQuery(doc, words);
+        }));
+    });
+}